@@ -0,0 +1,70 @@
+//! Rebuilding the mesh data of a large terminal after it's been cleared,
+//! which is done in bulk, compared to after every tile was written one at a
+//! time.
+//!
+//! Run with `cargo bench --bench clear`.
+
+use bevy::{
+    prelude::*,
+    render::{
+        camera::ManualTextureViews, deterministic::DeterministicRenderingConfig,
+        view::VisibilityPlugin,
+    },
+    transform::TransformPlugin,
+};
+use bevy_ascii_terminal::prelude::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const SIZE: [u32; 2] = [300, 200];
+
+/// A headless app with a single terminal.
+fn terminal_app() -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        TransformPlugin,
+        HierarchyPlugin,
+    ))
+    .init_asset::<Shader>()
+    .init_asset::<Image>()
+    .init_asset::<Mesh>()
+    .init_resource::<DeterministicRenderingConfig>()
+    .init_resource::<ManualTextureViews>()
+    .add_plugins((VisibilityPlugin, TerminalPlugin));
+
+    let e = app
+        .world
+        .spawn(TerminalBundle::from(Terminal::new(SIZE)))
+        .id();
+    app.update();
+    app.update();
+    (app, e)
+}
+
+fn clear(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clear 300x200");
+
+    let (mut app, e) = terminal_app();
+    group.bench_function("bulk", |b| {
+        b.iter(|| {
+            app.world.get_mut::<Terminal>(e).unwrap().clear();
+            app.update();
+        })
+    });
+
+    let (mut app, e) = terminal_app();
+    group.bench_function("per tile", |b| {
+        b.iter(|| {
+            let mut term = app.world.get_mut::<Terminal>(e).unwrap();
+            let tile = term.clear_tile;
+            term.fill_with(|_| tile);
+            app.update();
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, clear);
+criterion_main!(benches);
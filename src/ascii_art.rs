@@ -0,0 +1,149 @@
+//! Loading ascii art from text files.
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::{App, Asset, AssetApp, Plugin},
+    reflect::TypePath,
+    utils::BoxedFuture,
+};
+
+use crate::Terminal;
+
+/// Adds an asset loader for ascii art stored in `.txt` or `.asc` files.
+///
+/// This isn't added by [`TerminalPlugin`](crate::TerminalPlugin) since
+/// `.txt` files could be meant for other loaders.
+///
+/// # Example
+///
+/// ```rust no_run
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::prelude::*;
+///
+/// #[derive(Resource)]
+/// struct Splash(Handle<AsciiArt>);
+///
+/// fn load(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.insert_resource(Splash(server.load("splash.txt")));
+/// }
+///
+/// fn spawn(mut commands: Commands, splash: Res<Splash>, art: Res<Assets<AsciiArt>>) {
+///     if let Some(art) = art.get(&splash.0) {
+///         commands.spawn(TerminalBundle::from(art.to_terminal()));
+///     }
+/// }
+/// ```
+pub struct AsciiArtPlugin;
+
+impl Plugin for AsciiArtPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<AsciiArt>()
+            .register_asset_loader(AsciiArtLoader);
+    }
+}
+
+/// A block of ascii art, loaded from a text file.
+#[derive(Asset, TypePath, Debug, Clone, Default, PartialEq)]
+pub struct AsciiArt {
+    lines: Vec<String>,
+}
+
+impl AsciiArt {
+    /// The lines of the art, from top to bottom.
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// The width of the longest line and the number of lines.
+    pub fn size(&self) -> [u32; 2] {
+        let width = self.lines.iter().map(|l| l.chars().count()).max();
+        [width.unwrap_or(0) as u32, self.lines.len() as u32]
+    }
+
+    /// Create a terminal which exactly fits the art and write the art to it.
+    pub fn to_terminal(&self) -> Terminal {
+        let [width, height] = self.size();
+        let mut term = Terminal::new([width.max(1), height.max(1)]);
+        for (i, line) in self.lines.iter().enumerate() {
+            term.put_string([0, height as i32 - 1 - i as i32], line.as_str());
+        }
+        term
+    }
+}
+
+impl From<&str> for AsciiArt {
+    fn from(text: &str) -> Self {
+        Self {
+            lines: text.lines().map(String::from).collect(),
+        }
+    }
+}
+
+/// Error returned when ascii art fails to load.
+#[derive(Debug)]
+pub enum AsciiArtLoaderError {
+    /// The file couldn't be read.
+    Io(std::io::Error),
+    /// The file isn't valid UTF-8.
+    Utf8(std::string::FromUtf8Error),
+}
+
+impl std::fmt::Display for AsciiArtLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AsciiArtLoaderError::Io(err) => write!(f, "Failed to read ascii art: {}", err),
+            AsciiArtLoaderError::Utf8(err) => write!(f, "Ascii art isn't valid UTF-8: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for AsciiArtLoaderError {}
+
+#[derive(Default)]
+struct AsciiArtLoader;
+
+impl AssetLoader for AsciiArtLoader {
+    type Asset = AsciiArt;
+    type Settings = ();
+    type Error = AsciiArtLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<AsciiArt, AsciiArtLoaderError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(AsciiArtLoaderError::Io)?;
+            let text = String::from_utf8(bytes).map_err(AsciiArtLoaderError::Utf8)?;
+            Ok(AsciiArt::from(text.as_str()))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["txt", "asc"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn art_to_terminal() {
+        let art = AsciiArt::from("");
+        assert_eq!([0, 0], art.size());
+        assert_eq!([1, 1], art.to_terminal().size().to_array());
+
+        let art = AsciiArt::from(" /\\_/\\\r\n( o.o )\n > ^ <");
+        assert_eq!([7, 3], art.size());
+
+        let term = art.to_terminal();
+        assert_eq!([7, 3], term.size().to_array());
+        let lines: Vec<_> = term.lines().collect();
+        assert_eq!(vec![" /\\_/\\", "( o.o )", " > ^ <"], lines);
+    }
+}
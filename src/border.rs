@@ -2,7 +2,10 @@
 
 // use crate::{Tile, FormattedTile, TileFormatter};
 
-use bevy::{prelude::Color, utils::HashMap};
+use bevy::{
+    prelude::{Color, Resource},
+    utils::HashMap,
+};
 
 /// Specifies the style of lines to use along the border of a box.
 #[derive(Debug, Clone, PartialEq)]
@@ -15,7 +18,15 @@ pub struct Border {
     pub top_right: char,
     pub bottom_left: char,
     pub bottom_right: char,
-    pub(crate) edge_strings: HashMap<Edge, AlignedString>,
+    /// An optional background color for every tile of the border, including
+    /// any border strings. If `None` the terminal's clear tile color is used.
+    pub bg_color: Option<Color>,
+    /// An optional foreground color for the edge and corner glyphs.
+    pub edge_fg_color: Option<Color>,
+    /// An optional background color for the edge and corner glyphs, takes
+    /// precedence over `bg_color`.
+    pub edge_bg_color: Option<Color>,
+    pub(crate) edge_strings: HashMap<Edge, Vec<AlignedString>>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -67,6 +78,9 @@ impl Border {
             top_right: '┐',
             bottom_left: '└',
             bottom_right: '┘',
+            bg_color: None,
+            edge_fg_color: None,
+            edge_bg_color: None,
             edge_strings: Default::default(),
         }
     }
@@ -86,6 +100,21 @@ impl Border {
         }
     }
 
+    /// A border with rounded corners.
+    ///
+    /// The rounded corner glyphs (`╭╮╰╯`) aren't part of code page 437, so
+    /// this requires a font with a custom [`UvMapping`](crate::UvMapping)
+    /// that includes them.
+    pub fn rounded() -> Border {
+        Border {
+            top_left: '╭',
+            top_right: '╮',
+            bottom_left: '╰',
+            bottom_right: '╯',
+            ..Default::default()
+        }
+    }
+
     /// Construct a new [Border] from the given string.
     ///
     /// The format of the string should match the example below. Line returns
@@ -139,14 +168,117 @@ impl Border {
         }
     }
 
+    /// Set the background color for every tile of the border.
+    pub fn with_bg(mut self, color: Color) -> Self {
+        self.bg_color = Some(color);
+        self
+    }
+
+    /// Set the colors for the edge and corner glyphs of the border. Border
+    /// strings are unaffected.
+    pub fn with_edge_colors(mut self, fg: Color, bg: Color) -> Self {
+        self.edge_fg_color = Some(fg);
+        self.edge_bg_color = Some(bg);
+        self
+    }
+
+    /// Set the title of the border, replacing any existing titles.
     pub fn with_title(mut self, title: impl Into<AlignedString>) -> Self {
-        self.edge_strings.insert(Edge::Top, title.into());
+        self.edge_strings.insert(Edge::Top, vec![title.into()]);
         self
     }
 
+    /// Change the text of the border's first title, adding a title if there
+    /// isn't one.
     pub fn set_title_string(&mut self, title: impl Into<String>) {
-        let string = self.edge_strings.entry(Edge::Top).or_default();
-        string.string = title.into();
+        let titles = self.edge_strings.entry(Edge::Top).or_default();
+        if titles.is_empty() {
+            titles.push(AlignedString::default());
+        }
+        titles[0].string = title.into();
+    }
+
+    /// Put a title on the left side of the top edge, replacing any existing
+    /// left aligned title.
+    ///
+    /// Titles can be placed on the left, center and right of the same edge,
+    /// IE: a header with a name on the left and a page count on the right.
+    /// If titles would overlap, the one added first is drawn and the other is
+    /// skipped.
+    pub fn put_title_left(&mut self, title: impl Into<AlignedString>) {
+        self.put_title(title.into().aligned(0.0));
+    }
+
+    /// Put a title in the center of the top edge, replacing any existing
+    /// centered title. See [`Border::put_title_left`].
+    pub fn put_title_center(&mut self, title: impl Into<AlignedString>) {
+        self.put_title(title.into().aligned(0.5));
+    }
+
+    /// Put a title on the right side of the top edge, replacing any existing
+    /// right aligned title. See [`Border::put_title_left`].
+    pub fn put_title_right(&mut self, title: impl Into<AlignedString>) {
+        self.put_title(title.into().aligned(1.0));
+    }
+
+    fn put_title(&mut self, title: AlignedString) {
+        let titles = self.edge_strings.entry(Edge::Top).or_default();
+        match titles.iter_mut().find(|t| t.align == title.align) {
+            Some(existing) => *existing = title,
+            None => titles.push(title),
+        }
+    }
+}
+
+/// A collection of named border styles, for reusing the same styles across
+/// many terminals.
+///
+/// Includes the built in `"single_line"`, `"double_line"` and `"rounded"`
+/// styles by default.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::prelude::*;
+///
+/// fn setup(mut styles: ResMut<BorderStyles>) {
+///     styles.register("heavy", Border::from_string("┏━┓┃┃┗━┛"));
+/// }
+///
+/// fn spawn(mut commands: Commands, styles: Res<BorderStyles>) {
+///     let mut term = Terminal::new([10, 5]);
+///     term.set_border(styles.get("heavy").unwrap().clone());
+///     commands.spawn(TerminalBundle::from(term));
+/// }
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct BorderStyles {
+    styles: HashMap<String, Border>,
+}
+
+impl Default for BorderStyles {
+    fn default() -> Self {
+        let mut styles = Self {
+            styles: Default::default(),
+        };
+        styles.register("single_line", Border::single_line());
+        styles.register("double_line", Border::double_line());
+        styles.register("rounded", Border::rounded());
+        styles
+    }
+}
+
+impl BorderStyles {
+    /// Register a border style with the given name, replacing any existing
+    /// style with the same name.
+    pub fn register(&mut self, name: impl Into<String>, border: Border) {
+        self.styles.insert(name.into(), border);
+    }
+
+    /// Retrieve the border style with the given name.
+    pub fn get(&self, name: &str) -> Option<&Border> {
+        self.styles.get(name)
     }
 }
 
@@ -273,4 +405,33 @@ mod test {
         assert_eq!(glyphs.bottom_left, '└');
         assert_eq!(glyphs.bottom_right, '┘');
     }
+
+    #[test]
+    fn put_titles() {
+        let mut border = Border::single_line();
+        border.put_title_left("Inventory");
+        border.put_title_right("1/3");
+        border.put_title_right("2/3");
+
+        let titles = &border.edge_strings[&Edge::Top];
+        assert_eq!(2, titles.len());
+        assert_eq!(0.0, titles[0].align);
+        assert_eq!("2/3", titles[1].string);
+        assert_eq!(1.0, titles[1].align);
+
+        border.set_title_string("Items");
+        assert_eq!("Items", border.edge_strings[&Edge::Top][0].string);
+    }
+
+    #[test]
+    fn border_styles() {
+        let mut styles = BorderStyles::default();
+        assert_eq!(Some(&Border::double_line()), styles.get("double_line"));
+        assert!(styles.get("heavy").is_none());
+
+        styles.register("heavy", Border::from_string("┏━┓┃┃┗━┛"));
+        let heavy = styles.get("heavy").unwrap();
+        assert_eq!('┏', heavy.top_left);
+        assert_eq!('━', heavy.bottom);
+    }
 }
@@ -0,0 +1,98 @@
+//! A debug overlay for showing performance information in a terminal.
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    prelude::{App, Commands, Component, Plugin, Query, Res, Startup, Update, With, Without},
+};
+
+use crate::{term_print, Terminal, TerminalBundle, TerminalLayer};
+
+/// Spawns a small terminal showing the current FPS, the number of terminals
+/// and the total number of tiles in those terminals.
+///
+/// The overlay terminal is spawned at the world origin and can be found and
+/// moved via the [`DebugOverlay`] component. Adds bevy's
+/// [`FrameTimeDiagnosticsPlugin`] if it hasn't already been added.
+///
+/// # Example
+///
+/// ```rust no_run
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::prelude::*;
+///
+/// App::new()
+///     .add_plugins((DefaultPlugins, TerminalPlugin, TerminalDebugOverlay))
+///     .run();
+/// ```
+pub struct TerminalDebugOverlay;
+
+impl Plugin for TerminalDebugOverlay {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        app.add_systems(Startup, spawn_overlay)
+            .add_systems(Update, update_overlay);
+    }
+}
+
+/// Marker component for the terminal spawned by [`TerminalDebugOverlay`].
+#[derive(Component, Debug, Default)]
+pub struct DebugOverlay;
+
+/// The overlay is drawn above any terminal on a lower layer.
+const OVERLAY_LAYER: i32 = 100;
+
+fn spawn_overlay(mut commands: Commands) {
+    commands.spawn((
+        TerminalBundle::from(Terminal::new([18, 3])),
+        TerminalLayer(OVERLAY_LAYER),
+        DebugOverlay,
+    ));
+}
+
+fn update_overlay(
+    diagnostics: Option<Res<DiagnosticsStore>>,
+    q_terms: Query<&Terminal, Without<DebugOverlay>>,
+    mut q_overlay: Query<&mut Terminal, With<DebugOverlay>>,
+) {
+    let fps = diagnostics
+        .as_ref()
+        .and_then(|d| d.get(&FrameTimeDiagnosticsPlugin::FPS))
+        .and_then(|fps| fps.smoothed());
+    let count = q_terms.iter().count();
+    let tiles: usize = q_terms.iter().map(|t| t.width() * t.height()).sum();
+
+    for mut term in &mut q_overlay {
+        term.clear();
+        match fps {
+            Some(fps) => term_print!(term, [0, 2], "FPS: {:.1}", fps),
+            None => term_print!(term, [0, 2], "FPS: --"),
+        }
+        term_print!(term, [0, 1], "Terminals: {}", count);
+        term_print!(term, [0, 0], "Tiles: {}", tiles);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::MinimalPlugins;
+
+    use super::*;
+
+    #[test]
+    fn overlay() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TerminalDebugOverlay));
+        app.world.spawn(Terminal::new([10, 10]));
+        app.world.spawn(Terminal::new([5, 2]));
+        app.update();
+        app.update();
+
+        let mut q_overlay = app.world.query_filtered::<&Terminal, With<DebugOverlay>>();
+        let term = q_overlay.single(&app.world);
+        let lines: Vec<_> = term.lines().collect();
+        assert!(lines[0].starts_with("FPS: "), "{}", lines[0]);
+        assert_eq!("Terminals: 2", lines[1]);
+        assert_eq!("Tiles: 110", lines[2]);
+    }
+}
@@ -1,9 +1,15 @@
-use bevy::prelude::{default, Bundle, Changed, Component, Query, With};
+use bevy::{
+    prelude::{
+        default, Bundle, Changed, Component, Deref, DerefMut, DetectChanges, DetectChangesMut,
+        IVec2, Or, Query, Res, Time, Transform, UVec2, With,
+    },
+    utils::HashMap,
+};
 use sark_grids::{GridPoint, Pivot, Size2d};
 
 use crate::{
     renderer::{self, TileScaling},
-    Border, Terminal, TerminalFont, TerminalLayout,
+    Border, StringFormatter, Terminal, TerminalFont, TerminalLayout, Tile, TileFormatter,
 };
 
 /// A bundle with all the required components for a terminal.
@@ -36,7 +42,7 @@ impl TerminalBundle {
     /// Set the initial size of the terminal.
     pub fn with_size(mut self, size: impl Size2d) -> Self {
         self.terminal.resize(size.as_array());
-        self.layout.set_size(size.as_ivec2());
+        self.layout.set_size(self.terminal.size().as_ivec2());
         self
     }
 
@@ -69,6 +75,13 @@ impl TerminalBundle {
         self
     }
 
+    /// Force terminal tiles to the given aspect ratio (width / height) when
+    /// using [`TileScaling::World`], regardless of the font's tile size.
+    pub fn with_aspect_override(mut self, aspect: f32) -> Self {
+        self.layout.aspect_override = Some(aspect);
+        self
+    }
+
     /// Sets the [TileScaling] for the terminal.
     pub fn with_tile_scaling(mut self, scaling: TileScaling) -> Self {
         self.layout.scaling = scaling;
@@ -86,3 +99,514 @@ pub(crate) fn clear_after_render(
 ) {
     q_term.iter_mut().for_each(|mut t| t.clear());
 }
+
+/// If this component is added to a terminal the terminal will automatically be
+/// cleared at the start of every frame, for drawing the terminal in an
+/// "immediate mode" style.
+///
+/// Terminals are cleared in the [`First`](bevy::prelude::First) schedule, so
+/// anything drawn in `PreUpdate`, `Update` or `PostUpdate` will be rendered
+/// that frame. If a tile is given the terminal is filled with that tile,
+/// otherwise it's cleared to the terminal's `clear_tile`.
+#[derive(Default, Debug, Clone, Copy, Component)]
+pub struct AutoClear(pub Option<Tile>);
+
+pub(crate) fn auto_clear(mut q_term: Query<(&mut Terminal, &AutoClear)>) {
+    for (mut term, clear) in &mut q_term {
+        match clear.0 {
+            Some(tile) => term.fill(tile),
+            None => term.clear(),
+        }
+    }
+}
+
+/// A scratch buffer for drawing to a terminal in an "immediate mode" style
+/// without losing the benefit of the renderer's change tracking.
+///
+/// Add this alongside a [`Terminal`] and describe the whole terminal every
+/// frame by drawing to the scratch buffer instead of the terminal itself. At
+/// the end of the frame, before the terminal is rendered, only the tiles that
+/// differ from the live terminal are written to it and the scratch buffer is
+/// cleared for the next frame. If nothing changed the live terminal isn't
+/// touched at all.
+///
+/// If the scratch buffer is a different size than the terminal the terminal
+/// is resized to match.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::*;
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         TerminalBundle::from(Terminal::new([20, 3])),
+///         TerminalScratch::new([20, 3]),
+///     ));
+/// }
+///
+/// fn draw(mut q_scratch: Query<&mut TerminalScratch>, time: Res<Time>) {
+///     let mut scratch = q_scratch.single_mut();
+///     scratch.put_string([0, 1], "Hello");
+///     term_print!(scratch, [0, 0], "{:.1}", time.elapsed_seconds());
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Default, Deref, DerefMut)]
+pub struct TerminalScratch(Terminal);
+
+impl TerminalScratch {
+    /// Create a scratch buffer of the given size. This should match the size
+    /// of the terminal it's drawing to.
+    pub fn new(size: impl Size2d) -> Self {
+        Self(Terminal::new(size))
+    }
+}
+
+pub(crate) fn apply_scratch(mut q_term: Query<(&mut Terminal, &mut TerminalScratch)>) {
+    for (mut term, mut scratch) in &mut q_term {
+        if term.size() != scratch.size() {
+            term.resize(scratch.size());
+        }
+        // Only deref mutably if a tile actually changed
+        for (xy, tile) in scratch.diff(&term) {
+            term.put_tile(xy, tile);
+        }
+        scratch.clear();
+    }
+}
+
+/// If this component is added to a terminal, the terminal's z position will
+/// always be kept at the given layer.
+///
+/// Unlike [`TerminalBundle::with_depth`] this persists, so overlapping
+/// terminals will keep a consistent draw order even as they are moved around.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct TerminalLayer(pub i32);
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_terminal_layer(
+    mut q_term: Query<
+        (&TerminalLayer, &mut Transform),
+        Or<(Changed<TerminalLayer>, Changed<Transform>)>,
+    >,
+) {
+    for (layer, mut transform) in &mut q_term {
+        let z = layer.0 as f32;
+        if transform.translation.z != z {
+            transform.translation.z = z;
+        }
+    }
+}
+
+/// Shifts the coordinate space of a terminal so it can be written to with
+/// arbitrary coordinates, IE: world positions in a scrolling map view.
+///
+/// The origin is the position of the terminal's bottom left tile. Positions
+/// passed to [`TerminalOrigin::put_char`], [`TerminalOrigin::put_string`] and
+/// [`TerminalOrigin::get_tile`] are relative to it, and anything which falls
+/// outside the terminal after being translated is clipped.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::*;
+///
+/// fn draw_player(mut q_term: Query<(&mut Terminal, &TerminalOrigin)>) {
+///     let (mut term, origin) = q_term.single_mut();
+///     origin.put_char(&mut term, [-5, 3], '@');
+///     origin.put_string(&mut term, [-5, 4], "Player");
+/// }
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct TerminalOrigin(pub IVec2);
+
+impl TerminalOrigin {
+    /// Convert a position relative to the origin to a local terminal position.
+    pub fn to_local(&self, xy: impl GridPoint) -> IVec2 {
+        xy.as_ivec2() - self.0
+    }
+
+    /// Convert a local terminal position to a position relative to the origin.
+    pub fn from_local(&self, xy: impl GridPoint) -> IVec2 {
+        xy.as_ivec2() + self.0
+    }
+
+    /// Insert a formatted character into the terminal at a position relative
+    /// to the origin. Does nothing if the position is outside the terminal.
+    pub fn put_char(&self, term: &mut Terminal, xy: impl GridPoint, writer: impl TileFormatter) {
+        let xy = self.to_local(xy);
+        if term.in_bounds(xy) {
+            term.put_char(xy, writer);
+        }
+    }
+
+    /// Write a formatted string to the terminal at a position relative to the
+    /// origin, as with [`Terminal::put_string`]. Any part of the string
+    /// outside the terminal is clipped.
+    ///
+    /// The position is translated before being written, so pivots aren't
+    /// supported.
+    pub fn put_string<'a>(
+        &self,
+        term: &mut Terminal,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+    ) {
+        term.put_string(self.to_local(xy), writer);
+    }
+
+    /// Retrieve the tile at a position relative to the origin, or `None` if
+    /// the position is outside the terminal.
+    pub fn get_tile<'a>(&self, term: &'a Terminal, xy: impl GridPoint) -> Option<&'a Tile> {
+        let xy = self.to_local(xy);
+        term.in_bounds(xy).then(|| term.get_tile(xy))
+    }
+}
+
+/// A single animated tile, cycling through a sequence of glyphs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileAnimation {
+    /// The glyphs to cycle through, in order.
+    pub frames: Vec<char>,
+    /// How many frames are shown per second.
+    pub fps: f32,
+}
+
+impl TileAnimation {
+    pub fn new(frames: impl IntoIterator<Item = char>, fps: f32) -> Self {
+        Self {
+            frames: frames.into_iter().collect(),
+            fps,
+        }
+    }
+
+    /// The glyph for the given point in time, in seconds.
+    pub fn glyph_at(&self, seconds: f32) -> Option<char> {
+        if self.frames.is_empty() {
+            return None;
+        }
+        let frame = (seconds * self.fps).max(0.0) as usize % self.frames.len();
+        Some(self.frames[frame])
+    }
+}
+
+/// If this component is added to a terminal the glyphs of the given tiles
+/// will be animated, IE: for flickering torches or moving water.
+///
+/// Only the glyph is changed, the tile colors are left as is. The terminal
+/// is only marked as changed on frames where a glyph actually changes.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let mut animated = AnimatedTiles::default();
+///     animated.insert([5, 5], TileAnimation::new(['≈', '~'], 2.0));
+///     commands.spawn((TerminalBundle::from(Terminal::new([10, 10])), animated));
+/// }
+/// ```
+#[derive(Default, Debug, Clone, Component)]
+pub struct AnimatedTiles {
+    pub tiles: HashMap<IVec2, TileAnimation>,
+}
+
+impl AnimatedTiles {
+    /// Animate the tile at the given position.
+    pub fn insert(&mut self, xy: impl GridPoint, animation: TileAnimation) {
+        self.tiles.insert(xy.as_ivec2(), animation);
+    }
+
+    /// Stop animating the tile at the given position.
+    pub fn remove(&mut self, xy: impl GridPoint) -> Option<TileAnimation> {
+        self.tiles.remove(&xy.as_ivec2())
+    }
+}
+
+pub(crate) fn update_animated_tiles(
+    time: Res<Time>,
+    mut q_term: Query<(&AnimatedTiles, &mut Terminal)>,
+) {
+    let seconds = time.elapsed_seconds();
+    for (animated, mut term) in &mut q_term {
+        let mut changed = false;
+        // Avoid triggering change detection unless a glyph actually changes
+        let term_ref = term.bypass_change_detection();
+        for (xy, animation) in animated.tiles.iter() {
+            let Some(glyph) = animation.glyph_at(seconds) else {
+                continue;
+            };
+            if !term_ref.in_bounds(*xy) || term_ref.get_char(*xy) == glyph {
+                continue;
+            }
+            term_ref.get_tile_mut(*xy).glyph = glyph;
+            changed = true;
+        }
+        if changed {
+            term.set_changed();
+        }
+    }
+}
+
+/// Displays lines of text centered in a terminal.
+///
+/// The terminal is cleared and the text is rewritten whenever the text or
+/// the terminal's size changes, so it stays centered if the terminal is
+/// resized.
+#[derive(Component, Debug, Clone, Default)]
+pub struct CenteredText {
+    pub lines: Vec<String>,
+    /// The terminal size the text was last written for.
+    written_size: Option<UVec2>,
+}
+
+impl CenteredText {
+    pub fn new(lines: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            lines: lines.into_iter().map(Into::into).collect(),
+            written_size: None,
+        }
+    }
+}
+
+pub(crate) fn update_centered_text(mut q_term: Query<(&mut CenteredText, &mut Terminal)>) {
+    for (mut text, mut term) in &mut q_term {
+        if !text.is_changed() && text.written_size == Some(term.size()) {
+            continue;
+        }
+        term.clear();
+        term.put_string([0, 0].pivot(Pivot::Center), text.lines.join("\n").as_str());
+        // Don't trigger change detection from recording the size
+        text.bypass_change_detection().written_size = Some(term.size());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::{App, Color, First, PostUpdate, ResMut, Resource, Transform, Update, Vec3};
+
+    use super::*;
+
+    #[test]
+    fn spawn_with_border() {
+        let mut app = App::new();
+        let term = Terminal::new([10, 3]).with_border(Border::double_line());
+        let e = app.world.spawn(TerminalBundle::from(term)).id();
+
+        let term = app.world.get::<Terminal>(e).unwrap();
+        assert_eq!(Some(&Border::double_line()), term.border());
+        let layout = app.world.get::<TerminalLayout>(e).unwrap();
+        assert_eq!(Some(&Border::double_line()), layout.border());
+    }
+
+    #[test]
+    fn layer_persists() {
+        let mut app = App::new();
+        app.add_systems(PostUpdate, update_terminal_layer);
+
+        let bottom = app
+            .world
+            .spawn((TerminalLayer(1), Transform::default()))
+            .id();
+        let top = app
+            .world
+            .spawn((TerminalLayer(5), Transform::default()))
+            .id();
+        app.update();
+
+        app.world.get_mut::<Transform>(bottom).unwrap().translation = Vec3::new(3.0, 2.0, 10.0);
+        app.update();
+
+        let bottom = app.world.get::<Transform>(bottom).unwrap().translation;
+        let top = app.world.get::<Transform>(top).unwrap().translation;
+        assert_eq!(Vec3::new(3.0, 2.0, 1.0), bottom);
+        assert_eq!(5.0, top.z);
+        assert!(bottom.z < top.z);
+    }
+
+    #[test]
+    fn terminal_origin() {
+        let mut term = Terminal::new([10, 10]);
+        let origin = TerminalOrigin(IVec2::new(-5, -5));
+
+        origin.put_char(&mut term, [0, 0], 'a');
+        assert_eq!('a', term.get_char([5, 5]));
+        origin.put_char(&mut term, [-5, 4], 'b');
+        assert_eq!('b', term.get_char([0, 9]));
+        assert_eq!('b', origin.get_tile(&term, [-5, 4]).unwrap().glyph);
+        assert_eq!(IVec2::new(-5, 4), origin.from_local([0, 9]));
+
+        // Clipped after translation
+        origin.put_char(&mut term, [5, 0], 'c');
+        origin.put_char(&mut term, [-6, 0], 'c');
+        assert_eq!(0, term.count_glyph('c'));
+        assert!(origin.get_tile(&term, [0, 5]).is_none());
+
+        origin.put_string(&mut term, [-7, -3], "Hello");
+        assert_eq!("llo", term.get_string([0, 2], 3));
+        origin.put_string(&mut term, [3, 1], "World");
+        assert_eq!("Wo", term.get_string([8, 6], 2));
+    }
+
+    #[test]
+    fn animated_tiles() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_systems(Update, update_animated_tiles);
+
+        let mut animated = AnimatedTiles::default();
+        animated.insert([1, 1], TileAnimation::new(['a', 'b', 'c'], 4.0));
+        let e = app.world.spawn((Terminal::new([3, 3]), animated)).id();
+
+        app.update();
+        assert_eq!('a', app.world.get::<Terminal>(e).unwrap().get_char([1, 1]));
+
+        // Still within the first frame
+        app.world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(200));
+        app.update();
+        assert_eq!('a', app.world.get::<Terminal>(e).unwrap().get_char([1, 1]));
+
+        app.world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(100));
+        app.update();
+        assert_eq!('b', app.world.get::<Terminal>(e).unwrap().get_char([1, 1]));
+
+        // Wraps back to the first frame
+        app.world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(500));
+        app.update();
+        assert_eq!('a', app.world.get::<Terminal>(e).unwrap().get_char([1, 1]));
+    }
+
+    /// The row index from the top and left/right margins of the first line
+    /// of text in the terminal.
+    fn text_margins(term: &Terminal) -> (usize, usize, usize) {
+        let (row, line) = term
+            .lines()
+            .enumerate()
+            .find(|(_, l)| !l.is_empty())
+            .unwrap();
+        let left = line.len() - line.trim_start().len();
+        let right = term.width() - line.chars().count();
+        (row, left, right)
+    }
+
+    #[test]
+    fn centered_text() {
+        let mut app = App::new();
+        app.add_systems(Update, update_centered_text);
+        let e = app
+            .world
+            .spawn((Terminal::new([10, 10]), CenteredText::new(["hello"])))
+            .id();
+        app.update();
+
+        let term = app.world.get::<Terminal>(e).unwrap();
+        let (row, left, right) = text_margins(term);
+        assert!(row == 4 || row == 5, "{row}");
+        assert!(left.abs_diff(right) <= 1, "{left} {right}");
+
+        // Resizing the terminal re-centers the text
+        app.world.get_mut::<Terminal>(e).unwrap().resize([21, 3]);
+        app.update();
+        let term = app.world.get::<Terminal>(e).unwrap();
+        assert_eq!((1, 8, 8), text_margins(term));
+
+        // Nothing is rewritten if nothing changed
+        app.update();
+        app.world.clear_trackers();
+        app.update();
+        assert!(!app
+            .world
+            .entity(e)
+            .get_ref::<Terminal>()
+            .unwrap()
+            .is_changed());
+    }
+
+    #[derive(Resource, Default)]
+    struct Drawn(Vec<String>);
+
+    #[test]
+    fn auto_clear() {
+        let mut app = App::new();
+        app.init_resource::<Drawn>()
+            .add_systems(First, super::auto_clear)
+            .add_systems(
+                Update,
+                |mut q_term: Query<&mut Terminal>, mut drawn: ResMut<Drawn>| {
+                    for mut term in &mut q_term {
+                        // Record what was on the terminal before drawing
+                        drawn.0.push(term.get_string([0, 0], 3));
+                        term.put_string([0, 0], "abc");
+                    }
+                },
+            );
+        app.world
+            .spawn((Terminal::new([3, 1]), AutoClear::default()));
+        app.update();
+        app.update();
+        assert_eq!(vec!["   ", "   "], app.world.resource::<Drawn>().0);
+
+        let mut tile = Tile::from('.');
+        tile.bg_color = Color::BLUE;
+        let e = app
+            .world
+            .spawn((Terminal::new([3, 1]), AutoClear(Some(tile))))
+            .id();
+        app.update();
+        assert!(app.world.resource::<Drawn>().0.contains(&"...".to_string()));
+        let term = app.world.get::<Terminal>(e).unwrap();
+        assert_eq!(' ', term.clear_tile.glyph);
+    }
+
+    #[test]
+    fn apply_scratch() {
+        let mut app = App::new();
+        app.add_systems(Update, super::apply_scratch);
+        let e = app
+            .world
+            .spawn((Terminal::new([5, 2]), TerminalScratch::new([5, 2])))
+            .id();
+        app.update();
+        let tick = app.world.change_tick();
+
+        // Nothing was drawn, so nothing changed
+        app.update();
+        let term = app.world.entity(e).get_ref::<Terminal>().unwrap();
+        assert!(!term
+            .last_changed()
+            .is_newer_than(tick, app.world.change_tick()));
+
+        app.world
+            .get_mut::<TerminalScratch>(e)
+            .unwrap()
+            .put_string([0, 0], "ab");
+        app.world
+            .get_mut::<Terminal>(e)
+            .unwrap()
+            .put_char([4, 1], 'z');
+        app.update();
+        let term = app.world.get::<Terminal>(e).unwrap();
+        assert_eq!("ab   ", term.get_string([0, 0], 5));
+        // Tiles not drawn to the scratch buffer are cleared
+        assert_eq!(' ', term.get_char([4, 1]));
+        assert_eq!(
+            ' ',
+            app.world
+                .get::<TerminalScratch>(e)
+                .unwrap()
+                .get_char([0, 0])
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Keyboard focus for terminal based widgets.
+use bevy::{
+    input::ButtonInput,
+    prelude::{
+        resource_exists, App, Component, DetectChanges, Entity, IntoSystemConfigs, KeyCode, Plugin,
+        Query, Ref, Res, ResMut, Resource, Update,
+    },
+};
+
+use crate::{Border, Terminal};
+
+pub(crate) struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TerminalFocus>().add_systems(
+            Update,
+            (
+                cycle_focus.run_if(resource_exists::<ButtonInput<KeyCode>>),
+                update_focus_indicator,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Marks an entity as able to receive keyboard focus.
+///
+/// Pressing `Tab` moves focus to the next focusable entity and `Shift+Tab`
+/// moves it to the previous one, wrapping around at either end. Entities
+/// are ordered by `order`, then by entity.
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct Focusable {
+    pub order: i32,
+}
+
+/// Tracks which [`Focusable`] entity currently has keyboard focus.
+#[derive(Resource, Default, Debug)]
+pub struct TerminalFocus {
+    focused: Option<Entity>,
+}
+
+impl TerminalFocus {
+    /// The currently focused entity, if any.
+    pub fn focused(&self) -> Option<Entity> {
+        self.focused
+    }
+
+    /// Returns true if the given entity has focus.
+    pub fn is_focused(&self, entity: Entity) -> bool {
+        self.focused == Some(entity)
+    }
+
+    /// Give focus to the given entity.
+    pub fn set(&mut self, entity: Entity) {
+        self.focused = Some(entity);
+    }
+
+    /// Remove focus from every entity.
+    pub fn clear(&mut self) {
+        self.focused = None;
+    }
+}
+
+/// Changes a terminal's border depending on whether it has focus, as a
+/// simple way to show which terminal is focused.
+#[derive(Component, Debug, Clone)]
+pub struct FocusIndicator {
+    /// The border to use while the terminal has focus.
+    pub focused: Border,
+    /// The border to use while the terminal doesn't have focus. If `None`
+    /// the border is removed.
+    pub unfocused: Option<Border>,
+}
+
+fn cycle_focus(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut focus: ResMut<TerminalFocus>,
+    q_focusable: Query<(Entity, &Focusable)>,
+) {
+    if !keys.just_pressed(KeyCode::Tab) {
+        return;
+    }
+    let mut entities: Vec<_> = q_focusable.iter().collect();
+    if entities.is_empty() {
+        return;
+    }
+    entities.sort_by_key(|(e, f)| (f.order, *e));
+
+    let len = entities.len();
+    let back = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let current = focus
+        .focused
+        .and_then(|focused| entities.iter().position(|(e, _)| *e == focused));
+    let next = match (current, back) {
+        (Some(i), false) => (i + 1) % len,
+        (Some(i), true) => (i + len - 1) % len,
+        (None, false) => 0,
+        (None, true) => len - 1,
+    };
+    focus.focused = Some(entities[next].0);
+}
+
+fn update_focus_indicator(
+    focus: Res<TerminalFocus>,
+    mut q_term: Query<(Entity, Ref<FocusIndicator>, &mut Terminal)>,
+) {
+    for (entity, indicator, mut term) in &mut q_term {
+        if !focus.is_changed() && !indicator.is_changed() {
+            continue;
+        }
+        let border = if focus.is_focused(entity) {
+            Some(&indicator.focused)
+        } else {
+            indicator.unfocused.as_ref()
+        };
+        if term.border() == border {
+            continue;
+        }
+        match border {
+            Some(border) => term.set_border(border.clone()),
+            None => term.remove_border(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Update;
+
+    use super::*;
+
+    fn press(app: &mut App, keys: &[KeyCode]) {
+        let mut input = app.world.resource_mut::<ButtonInput<KeyCode>>();
+        input.reset_all();
+        for key in keys {
+            input.press(*key);
+        }
+        app.update();
+    }
+
+    #[test]
+    fn tab_cycles_focus() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .add_plugins(FocusPlugin);
+
+        let b = app.world.spawn(Focusable { order: 1 }).id();
+        let a = app.world.spawn(Focusable { order: 0 }).id();
+        let c = app.world.spawn(Focusable { order: 2 }).id();
+
+        app.update();
+        assert_eq!(None, app.world.resource::<TerminalFocus>().focused());
+
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(Some(a), app.world.resource::<TerminalFocus>().focused());
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(Some(b), app.world.resource::<TerminalFocus>().focused());
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(Some(c), app.world.resource::<TerminalFocus>().focused());
+        // Wraps back to the first entity
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(Some(a), app.world.resource::<TerminalFocus>().focused());
+        // Shift+Tab moves backwards
+        press(&mut app, &[KeyCode::ShiftLeft, KeyCode::Tab]);
+        assert_eq!(Some(c), app.world.resource::<TerminalFocus>().focused());
+    }
+
+    #[test]
+    fn focus_indicator() {
+        let mut app = App::new();
+        app.init_resource::<TerminalFocus>()
+            .add_systems(Update, update_focus_indicator);
+
+        let indicator = FocusIndicator {
+            focused: Border::double_line(),
+            unfocused: None,
+        };
+        let a = app
+            .world
+            .spawn((Terminal::new([3, 3]), indicator.clone()))
+            .id();
+        let b = app.world.spawn((Terminal::new([3, 3]), indicator)).id();
+
+        app.world.resource_mut::<TerminalFocus>().set(a);
+        app.update();
+        let border = app.world.get::<Terminal>(a).unwrap().border().cloned();
+        assert_eq!(Some(Border::double_line()), border);
+        assert!(app.world.get::<Terminal>(b).unwrap().border().is_none());
+
+        app.world.resource_mut::<TerminalFocus>().set(b);
+        app.update();
+        assert!(app.world.get::<Terminal>(a).unwrap().border().is_none());
+        assert!(app.world.get::<Terminal>(b).unwrap().border().is_some());
+    }
+}
@@ -18,6 +18,58 @@ pub trait StringFormatter<'a>: Clone {
     fn fg(self, color: Color) -> FormattedString<'a>; //FormattedString<'a>;
     /// Change the background color.
     fn bg(self, color: Color) -> FormattedString<'a>;
+    /// Only write the string's colors, leaving the existing glyphs in the
+    /// terminal unchanged.
+    ///
+    /// The string is still laid out as normal, so this can be used to recolor
+    /// an area the size of the string.
+    fn colors_only(self) -> FormattedString<'a> {
+        let mut fmt = self.formatted();
+        fmt.colors_only = true;
+        fmt
+    }
+    /// Highlight the area covered by the string.
+    ///
+    /// Sets the background color of every tile the string occupies,
+    /// including spaces, without changing the existing glyphs or foreground
+    /// colors. Useful for rendering a text selection.
+    fn highlight(self, color: Color) -> FormattedString<'a> {
+        self.colors_only().bg(color)
+    }
+
+    /// Write the string as an overlay: only glyphs and foreground colors are
+    /// written, the background color of every tile is always left as is.
+    ///
+    /// Strings without a background color already leave the existing
+    /// background alone. An overlay guarantees it, ignoring any background
+    /// color set on the string, IE: for labels drawn over a colored map.
+    fn overlay(self) -> FormattedString<'a> {
+        let mut fmt = self.formatted();
+        fmt.overlay = true;
+        fmt
+    }
+
+    /// Limit the number of lines written, including lines created by
+    /// wrapping with [`Terminal::put_string_in`](crate::Terminal::put_string_in).
+    /// Any lines past the limit are dropped.
+    fn max_lines(self, max_lines: usize) -> FormattedString<'a> {
+        let mut fmt = self.formatted();
+        fmt.max_lines = Some(max_lines);
+        fmt
+    }
+
+    /// Fill `width` tiles from the start of each line with the given
+    /// background color, regardless of the line's length, IE: for menu
+    /// entries with evenly sized backgrounds. Lines longer than `width` are
+    /// still written in full, and a background color set on the string
+    /// itself takes precedence over the fill. With
+    /// [`Terminal::put_string_in`](crate::Terminal::put_string_in) each
+    /// wrapped line is filled, clipped to the area.
+    fn fill_width(self, width: usize, bg: Color) -> FormattedString<'a> {
+        let mut fmt = self.formatted();
+        fmt.fill_width = Some((width, bg));
+        fmt
+    }
 
     /// Get the formatted string.
     fn formatted(self) -> FormattedString<'a>;
@@ -29,6 +81,15 @@ pub trait StringFormatter<'a>: Clone {
 pub struct FormattedString<'a> {
     pub string: Cow<'a, str>,
     pub writes: ArrayVec<StringModifier, 3>,
+    /// If true, only colors will be written to the terminal.
+    pub colors_only: bool,
+    /// If true, background colors will never be written to the terminal.
+    pub overlay: bool,
+    /// The maximum number of lines to write, if any.
+    pub max_lines: Option<usize>,
+    /// The number of tiles to fill with a background color from the start
+    /// of each line, if any.
+    pub fill_width: Option<(usize, Color)>,
 }
 
 impl<'a> FormattedString<'a> {
@@ -40,6 +101,30 @@ impl<'a> FormattedString<'a> {
     }
 }
 
+impl<'a> FormattedString<'a> {
+    /// The part of the string that will be written, accounting for
+    /// `max_lines`.
+    pub(crate) fn visible(&self) -> &str {
+        let string = self.string.as_ref();
+        match self.max_lines {
+            Some(0) => "",
+            Some(max) => match string.match_indices('\n').nth(max - 1) {
+                Some((i, _)) => &string[..i],
+                None => string,
+            },
+            None => string,
+        }
+    }
+
+    /// Write a glyph of the string to a tile, along with the string's colors.
+    pub(crate) fn write(&self, tile: &mut Tile, glyph: char) {
+        if !self.colors_only {
+            tile.glyph = glyph;
+        }
+        self.apply(tile);
+    }
+}
+
 impl<'a> StringFormatter<'a> for FormattedString<'a> {
     fn string(&self) -> &str {
         self.string.as_ref()
@@ -63,7 +148,8 @@ impl<'a> StringFormatter<'a> for FormattedString<'a> {
         for write in &self.writes {
             match write {
                 StringModifier::FgColor(col) => tile.fg_color = *col,
-                StringModifier::BgColor(col) => tile.bg_color = *col,
+                StringModifier::BgColor(col) if !self.overlay => tile.bg_color = *col,
+                StringModifier::BgColor(_) => {}
             }
         }
     }
@@ -117,15 +203,15 @@ impl<'a> StringFormatter<'a> for &'a String {
     }
 
     fn fg(self, color: Color) -> FormattedString<'a> {
-        FormattedString::new(self.to_owned()).fg(color)
+        FormattedString::new(self.as_str()).fg(color)
     }
 
     fn formatted(self) -> FormattedString<'a> {
-        FormattedString::new(self.to_owned())
+        FormattedString::new(self.as_str())
     }
 
     fn bg(self, color: Color) -> FormattedString<'a> {
-        FormattedString::new(self.to_owned()).bg(color)
+        FormattedString::new(self.as_str()).bg(color)
     }
 
     fn apply(&self, _tile: &mut Tile) {}
@@ -140,11 +226,11 @@ impl<'a> From<FormattedString<'a>> for (Cow<'a, str>, ArrayVec<StringModifier, 3
 #[cfg(test)]
 mod test {
     use bevy::prelude::Color;
-    use sark_grids::{GridPoint, Pivot};
+    use sark_grids::{geometry::GridRect, GridPoint, Pivot};
 
     use crate::Terminal;
 
-    use super::StringFormatter;
+    use super::{Cow, StringFormatter};
 
     #[test]
     fn string_color() {
@@ -154,6 +240,32 @@ mod test {
         assert_eq!(term.get_tile([5, 5]).fg_color, Color::GREEN);
     }
 
+    #[test]
+    fn highlight() {
+        let mut term = Terminal::new([15, 5]);
+        term.put_string([1, 1], "Hello world".fg(Color::GREEN));
+        term.put_string([1, 1], "           ".highlight(Color::BLUE));
+
+        assert_eq!("Hello world", term.get_string([1, 1], 11));
+        for x in 1..12 {
+            let t = term.get_tile([x, 1]);
+            assert_eq!(Color::BLUE, t.bg_color);
+            assert_eq!(Color::GREEN, t.fg_color);
+        }
+        assert_ne!(Color::BLUE, term.get_tile([12, 1]).bg_color);
+    }
+
+    #[test]
+    fn borrow_string_ref() {
+        let string = String::from("Hello");
+        let fmt = (&string).fg(Color::RED);
+        assert!(matches!(fmt.string, Cow::Borrowed(_)));
+
+        let mut term = Terminal::new([10, 1]);
+        term.put_string([0, 0], &string);
+        assert_eq!("Hello", term.get_string([0, 0], 5));
+    }
+
     #[test]
     fn pivot_top_right() {
         let mut term = Terminal::new([20, 20]);
@@ -235,4 +347,114 @@ mod test {
         assert_eq!("How are you?", term.get_string([5, 10], 12));
         assert_eq!("Ok", term.get_string([10, 9], 2));
     }
+
+    #[test]
+    fn pivot_center_paragraphs() {
+        let mut term = Terminal::new([12, 5]).with_clear_tile('.');
+        term.put_string(
+            [0, 0].pivot(Pivot::Center),
+            "Welcome to\nthe dungeon\n\nPress start",
+        );
+
+        // Each line is centered on its own, including the blank line
+        // between paragraphs
+        term.assert_looks_like(
+            "
+............
+..Welcome to
+.the dungeon
+............
+.Press start",
+        );
+    }
+
+    #[test]
+    fn preserve_indent() {
+        let mut term = Terminal::new([20, 20]).with_clear_tile('.');
+        term.put_string([0, 0].pivot(Pivot::TopLeft), "line1\n  indented");
+
+        assert_eq!("line1", term.get_string([0, 19], 5));
+        assert_eq!("  indented", term.get_string([0, 18], 10));
+    }
+
+    #[test]
+    fn overlay() {
+        let mut term = Terminal::new([10, 1]);
+        term.put_string([0, 0], "          ".bg(Color::BLUE));
+        term.put_string([1, 0], "abc".fg(Color::RED).bg(Color::GREEN).overlay());
+
+        assert_eq!("abc", term.get_string([1, 0], 3));
+        for x in 0..10 {
+            assert_eq!(Color::BLUE, term.get_tile([x, 0]).bg_color);
+        }
+        assert_eq!(Color::RED, term.get_tile([2, 0]).fg_color);
+        assert_eq!(Color::WHITE, term.get_tile([4, 0]).fg_color);
+    }
+
+    #[test]
+    fn max_lines() {
+        let mut term = Terminal::new([10, 5]).with_clear_tile('.');
+        term.put_string([0, 2], "a\nb\nc\nd\ne".max_lines(3));
+        term.assert_looks_like(
+            "
+a.........
+b.........
+c.........
+..........
+..........",
+        );
+
+        // Overflow is reported as dropped glyphs
+        term.clear();
+        assert_eq!(
+            2,
+            term.put_string_checked([0, 2], "a\nb\nc\nd\ne".max_lines(3))
+        );
+        assert_eq!(0, term.put_string_checked([0, 2], "a\nb".max_lines(3)));
+    }
+
+    #[test]
+    fn fill_width() {
+        let mut term = Terminal::new([12, 2]);
+        term.put_string([1, 0], "New".fg(Color::RED).fill_width(10, Color::BLUE));
+
+        assert_eq!("New", term.get_string([1, 0], 3));
+        let filled: Vec<_> = (0..12)
+            .filter(|x| term.get_tile([*x, 0]).bg_color == Color::BLUE)
+            .collect();
+        assert_eq!((1..11).collect::<Vec<_>>(), filled);
+        // Only the text itself gets the foreground color
+        assert_eq!(Color::WHITE, term.get_tile([4, 0]).fg_color);
+        assert!(term.iter_row(1).all(|t| t.bg_color == Color::BLACK));
+
+        // The string's own background color takes precedence
+        term.put_string([0, 1], "ab".bg(Color::RED).fill_width(4, Color::BLUE));
+        assert_eq!(Color::RED, term.get_tile([1, 1]).bg_color);
+        assert_eq!(Color::BLUE, term.get_tile([2, 1]).bg_color);
+
+        // Each wrapped line is filled, but not past the edge of the area
+        let mut term = Terminal::new([12, 3]);
+        let rect = GridRect::from_bl([2, 0], [6u32, 3]);
+        term.put_string_in(rect, [2, 2], "ab cd ef".fill_width(8, Color::BLUE));
+        for y in [2, 1] {
+            let filled: Vec<_> = (0..12)
+                .filter(|x| term.get_tile([*x, y]).bg_color == Color::BLUE)
+                .collect();
+            assert_eq!((2..8).collect::<Vec<_>>(), filled);
+        }
+        assert!(term.iter_row(0).all(|t| t.bg_color == Color::BLACK));
+    }
+
+    #[test]
+    fn colors_only() {
+        let mut term = Terminal::new([10, 10]);
+        term.put_string([0, 0], "Hello");
+        term.put_string([1, 0], "xxx".bg(Color::BLUE).colors_only());
+
+        assert_eq!("Hello", term.get_string([0, 0], 5));
+        assert_eq!(Color::BLACK, term.get_tile([0, 0]).bg_color);
+        assert_eq!(Color::BLUE, term.get_tile([1, 0]).bg_color);
+        assert_eq!(Color::BLUE, term.get_tile([3, 0]).bg_color);
+        assert_eq!(Color::BLACK, term.get_tile([4, 0]).bg_color);
+    }
 }
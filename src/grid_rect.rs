@@ -0,0 +1,65 @@
+//! Helpers for positioning a [`GridRect`] relative to another.
+
+use bevy::math::Vec2;
+use sark_grids::{geometry::GridRect, Pivot};
+
+/// Extension methods for aligning a [`GridRect`], IE: for placing a smaller
+/// panel inside a larger terminal.
+pub trait GridRectAlign {
+    /// Returns a rect with this rect's size, positioned within `container`
+    /// according to the given pivot.
+    ///
+    /// If the rect can't be aligned exactly (IE: centering an odd sized rect
+    /// in an even sized container) the position is rounded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let container = GridRect::from_bl([0, 0], [10, 10]);
+    /// let panel = GridRect::origin([4, 2]).aligned_within(container, Pivot::TopRight);
+    /// assert_eq!(container.max_i(), panel.max_i());
+    /// ```
+    fn aligned_within(&self, container: GridRect, pivot: Pivot) -> GridRect;
+}
+
+impl GridRectAlign for GridRect {
+    fn aligned_within(&self, container: GridRect, pivot: Pivot) -> GridRect {
+        let pivot = Vec2::from(pivot);
+        let space = (container.size() - self.size()).as_vec2();
+        let offset = (space * pivot).round().as_ivec2();
+        GridRect::from_bl(container.min_i() + offset, self.size().as_uvec2())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::IVec2;
+
+    use super::*;
+
+    #[test]
+    fn center_odd_in_even() {
+        let container = GridRect::from_bl([0, 0], [10, 10]);
+        let rect = GridRect::origin([3, 3]).aligned_within(container, Pivot::Center);
+
+        assert_eq!(IVec2::new(3, 3), rect.size());
+        assert_eq!(IVec2::new(4, 4), rect.min_i());
+    }
+
+    #[test]
+    fn corners() {
+        let container = GridRect::from_bl([5, 5], [10, 8]);
+        let rect = GridRect::origin([3, 2]);
+
+        let bl = rect.aligned_within(container, Pivot::BottomLeft);
+        assert_eq!(container.min_i(), bl.min_i());
+
+        let tr = rect.aligned_within(container, Pivot::TopRight);
+        assert_eq!(container.max_i(), tr.max_i());
+
+        let tl = rect.aligned_within(container, Pivot::TopLeft);
+        assert_eq!(IVec2::new(5, 11), tl.min_i());
+    }
+}
@@ -41,6 +41,52 @@
 //! }
 //! ```
 //!
+//! ## Rendering without [`AutoCamera`]
+//!
+//! Terminals can also be rendered by a regular bevy camera. By default each
+//! terminal tile is one world unit tall, so for bevy's default orthographic
+//! camera you will want to use [`TileScaling::Pixels`], where one pixel of the
+//! font texture is one world unit.
+//!
+//! ```rust no_run
+//! use bevy::prelude::*;
+//! use bevy_ascii_terminal::*;
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn(Camera2dBundle::default());
+//!
+//!     let mut terminal = Terminal::new([20,3]);
+//!     terminal.put_string([1, 1], "Hello world!");
+//!     commands.spawn(
+//!         TerminalBundle::from(terminal).with_tile_scaling(TileScaling::Pixels)
+//!     );
+//! }
+//! ```
+//!
+//! ## Positions and sizes
+//!
+//! Functions taking a position accept anything implementing [`GridPoint`],
+//! such as `[i32; 2]`, `[u32; 2]`, `[usize; 2]`, `IVec2`, `UVec2` or a
+//! pivoted point. Sizes accept anything implementing [`Size2d`] such as
+//! `[u32; 2]`, `[usize; 2]` or `UVec2`.
+//!
+//! Tuples aren't accepted directly - both traits come from `sark_grids`, so
+//! this crate can't implement them for tuples. A tuple can be converted
+//! to an array or vector with `into`:
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_ascii_terminal::*;
+//!
+//! let size: [u32; 2] = (80, 25).into();
+//! let mut terminal = Terminal::new(size);
+//!
+//! let pos = (1, 1);
+//! terminal.put_char(IVec2::from(pos), 'x');
+//! terminal.put_char(<[i32; 2]>::from(pos), 'y');
+//! assert_eq!('y', terminal.get_char([1, 1]));
+//! ```
+//!
 //! ## Versions
 //! | bevy  | bevy_ascii_terminal |
 //! | ----- | ------------------- |
@@ -51,22 +97,38 @@
 //! | 0.8.1 | 0.11.1-4            |
 //! | 0.8   | 0.11                |
 //! | 0.7   | 0.9-0.10            |
+mod ascii_art;
 mod border;
+#[cfg(feature = "debug")]
+mod debug;
 mod entity;
+mod focus;
 mod formatting;
+mod grid_rect;
 mod renderer;
+#[cfg(feature = "rexpaint")]
+mod rexpaint;
 mod terminal;
 mod to_world;
+mod widget;
 
-use bevy::prelude::{App, IntoSystemConfigs, Last, Plugin};
+use bevy::{
+    prelude::{App, First, IntoSystemConfigs, Last, Plugin, PostUpdate, Update},
+    transform::TransformSystem,
+};
 #[cfg(feature = "camera")]
-pub use renderer::{AutoCamera, TiledCamera, TiledCameraBundle};
+pub use renderer::{
+    AutoCamera, TerminalCamera, TerminalCameraViewport, TiledCamera, TiledCameraBundle,
+};
+
+pub use renderer::{code_page_437, TerminalFont, TerminalLayout, TerminalMaterial, UvMapping};
 
-pub use renderer::{code_page_437, TerminalFont, TerminalLayout, TerminalMaterial};
+#[cfg(feature = "bitmap_fonts")]
+pub use renderer::BitmapFontError;
 
 pub use to_world::ToWorld;
 
-pub use sark_grids::{grid::Side, GridPoint, Pivot, Size2d};
+pub use sark_grids::{geometry::GridRect, grid::Side, GridPoint, Pivot, Size2d};
 
 /// The primary terminal rendering function labels
 pub use crate::renderer::{
@@ -77,17 +139,30 @@ pub use crate::renderer::{
 pub use prelude::*;
 
 pub mod prelude {
+    #[cfg(feature = "debug")]
+    pub use crate::debug::{DebugOverlay, TerminalDebugOverlay};
     #[cfg(feature = "camera")]
-    pub use crate::renderer::{AutoCamera, TileScaling};
+    pub use crate::renderer::AutoCamera;
+    pub use crate::renderer::{
+        TerminalBatch, TerminalBatchBundle, TerminalScrollOffset, TileScaling,
+    };
+    #[cfg(feature = "rexpaint")]
+    pub use crate::rexpaint::{XpArt, XpArtPlugin};
     pub use crate::{
-        border::{AlignedStringFormatter, Border, Edge},
-        entity::ClearAfterRender,
+        ascii_art::{AsciiArt, AsciiArtLoaderError, AsciiArtPlugin},
+        border::{AlignedStringFormatter, Border, BorderStyles, Edge},
         entity::TerminalBundle,
+        entity::TerminalLayer,
+        entity::{AnimatedTiles, CenteredText, TileAnimation},
+        entity::{AutoClear, ClearAfterRender, TerminalOrigin, TerminalScratch},
+        focus::{FocusIndicator, Focusable, TerminalFocus},
         formatting::*,
-        terminal::{Terminal, Tile},
+        grid_rect::GridRectAlign,
+        terminal::{FloodFillOptions, Terminal, TerminalSizeError, Tile, TileCountError},
+        widget::TerminalWidget,
         TerminalPlugin,
     };
-    pub use sark_grids::{grid::Side, GridPoint, Pivot, Size2d};
+    pub use sark_grids::{geometry::GridRect, grid::Side, GridPoint, Pivot, Size2d};
 }
 
 /// Plugin for terminal rendering and related components and systems.
@@ -95,7 +170,24 @@ pub struct TerminalPlugin;
 
 impl Plugin for TerminalPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((renderer::TerminalRendererPlugin, to_world::ToWorldPlugin))
-            .add_systems(Last, entity::clear_after_render.after(TerminalRender));
+        app.add_plugins((
+            renderer::TerminalRendererPlugin,
+            to_world::ToWorldPlugin,
+            focus::FocusPlugin,
+        ));
+        #[cfg(feature = "camera")]
+        app.add_plugins(renderer::TerminalCameraPlugin);
+        app.init_resource::<BorderStyles>()
+            .add_systems(
+                Update,
+                (entity::update_animated_tiles, entity::update_centered_text),
+            )
+            .add_systems(First, entity::auto_clear)
+            .add_systems(Last, entity::apply_scratch.before(renderer::MeshInit))
+            .add_systems(Last, entity::clear_after_render.after(TerminalRender))
+            .add_systems(
+                PostUpdate,
+                entity::update_terminal_layer.before(TransformSystem::TransformPropagate),
+            );
     }
 }
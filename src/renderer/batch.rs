@@ -0,0 +1,156 @@
+//! Rendering several small terminals with a single mesh.
+//!
+//! Each terminal normally gets its own mesh, and therefore its own draw
+//! call. For scenes with many small terminals (tooltips, labels, etc) a
+//! [`TerminalBatch`] can be used instead - the tiles of every child terminal
+//! are written into one combined mesh which shares the batch's font and
+//! [`UvMapping`].
+
+use bevy::prelude::{
+    Assets, Bundle, Children, Component, DetectChanges, Handle, Query, Ref, Res, Transform, With,
+};
+
+use sark_grids::Size2d;
+
+use crate::{Terminal, TerminalFont, TerminalLayout};
+
+use super::{
+    mesh_data::{TileData, UvMesher, VertData, VertMesher},
+    uv_mapping::UvMapping,
+    TerminalRenderBundle,
+};
+
+/// Merges all child terminals of this entity into a single mesh.
+///
+/// Child terminals only need a [`Terminal`] and a [`Transform`] (IE: a
+/// `TransformBundle`), they should not have their own renderer. Each
+/// terminal is centered on its transform's translation, in the batch's
+/// local space. All terminals in a batch use the batch's font and tile size.
+///
+/// Terminal borders are not drawn for batched terminals.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::*;
+///
+/// fn setup(mut commands: Commands) {
+///     commands
+///         .spawn(TerminalBatchBundle::default())
+///         .with_children(|batch| {
+///             let mut label = Terminal::new([5, 1]);
+///             label.put_string([0, 0], "Hello");
+///             batch.spawn((label, TransformBundle::default()));
+///
+///             let mut label = Terminal::new([5, 1]);
+///             label.put_string([0, 0], "World");
+///             let pos = Transform::from_xyz(0.0, -2.0, 0.0);
+///             batch.spawn((label, TransformBundle::from_transform(pos)));
+///         });
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, Component)]
+pub struct TerminalBatch;
+
+/// A bundle with all the required components for a [`TerminalBatch`].
+#[derive(Bundle, Default)]
+pub struct TerminalBatchBundle {
+    pub batch: TerminalBatch,
+    pub renderer: TerminalRenderBundle,
+    pub layout: TerminalLayout,
+    pub font: TerminalFont,
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_batch(
+    mut q_batch: Query<
+        (
+            Ref<TerminalLayout>,
+            Ref<Children>,
+            &Handle<UvMapping>,
+            &mut VertData,
+            &mut TileData,
+        ),
+        With<TerminalBatch>,
+    >,
+    q_term: Query<(Ref<Terminal>, Ref<Transform>)>,
+    mappings: Res<Assets<UvMapping>>,
+) {
+    for (layout, children, mapping, mut verts, mut tiles) in &mut q_batch {
+        let terminals = || children.iter().filter_map(|child| q_term.get(*child).ok());
+        let changed = layout.is_changed()
+            || children.is_changed()
+            || terminals().any(|(term, transform)| term.is_changed() || transform.is_changed());
+        if !changed {
+            continue;
+        }
+        let Some(mapping) = mappings.get(mapping) else {
+            continue;
+        };
+
+        verts.clear();
+        tiles.clear();
+
+        for (term, transform) in terminals() {
+            let size = term.size().as_vec2() * layout.tile_size;
+            let origin = transform.translation.truncate() - size / 2.0;
+
+            // Verts and uvs must be added in the same order
+            let mut vmesher = VertMesher::new(origin, layout.tile_size, &mut verts);
+            for i in 0..term.size().len() {
+                let x = i % term.width();
+                let y = i / term.width();
+                vmesher.tile_verts_at([x, y]);
+            }
+
+            let mut tmesher = UvMesher::new(mapping, &mut tiles);
+            for tile in term.iter() {
+                tmesher.tile_uvs(tile.glyph, tile.fg_color, tile.bg_color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{prelude::*, sprite::Mesh2dHandle, transform::TransformPlugin};
+
+    use crate::renderer::TerminalRendererPlugin;
+
+    use super::*;
+
+    #[test]
+    fn combined_vertex_count() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            TransformPlugin,
+            HierarchyPlugin,
+        ))
+        .init_asset::<Shader>()
+        .init_asset::<Image>()
+        .init_asset::<Mesh>()
+        .add_plugins(TerminalRendererPlugin);
+
+        let batch = app
+            .world
+            .spawn(TerminalBatchBundle::default())
+            .with_children(|batch| {
+                batch.spawn((Terminal::new([10, 3]), TransformBundle::default()));
+                let pos = Transform::from_xyz(0.0, 5.0, 0.0);
+                batch.spawn((Terminal::new([4, 2]), TransformBundle::from_transform(pos)));
+            })
+            .id();
+
+        app.update();
+        app.update();
+
+        let handle = app.world.get::<Mesh2dHandle>(batch).unwrap();
+        let meshes = app.world.resource::<Assets<Mesh>>();
+        let mesh = meshes.get(&handle.0).unwrap();
+        assert_eq!((10 * 3 + 4 * 2) * 4, mesh.count_vertices());
+        assert_eq!((10 * 3 + 4 * 2) * 6, mesh.indices().unwrap().len());
+    }
+}
@@ -0,0 +1,351 @@
+//! Loading legacy bitmap console fonts (`.psf`/`.bdf`) into a terminal font
+//! atlas.
+//!
+//! The glyphs are rasterized into a 16x16 Code Page 437 atlas [`Image`], the
+//! same layout as the built in fonts.
+
+use bevy::{
+    math::UVec2,
+    prelude::Image,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::{ImageSampler, ImageSamplerDescriptor},
+    },
+};
+
+use super::{code_page_437, TerminalFont};
+
+/// Error returned when a bitmap font couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitmapFontError {
+    /// The data didn't start with a known PSF header.
+    UnknownFormat,
+    /// The data ended before all the glyphs could be read.
+    Truncated,
+    /// The BDF font was malformed.
+    InvalidBdf(String),
+}
+
+impl std::fmt::Display for BitmapFontError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BitmapFontError::UnknownFormat => write!(f, "Unrecognized bitmap font header"),
+            BitmapFontError::Truncated => write!(f, "Bitmap font data ended unexpectedly"),
+            BitmapFontError::InvalidBdf(err) => write!(f, "Invalid BDF font: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for BitmapFontError {}
+
+impl TerminalFont {
+    /// Rasterize a PSF (version 1 or 2) console font into a font atlas.
+    ///
+    /// The glyphs are assumed to be in Code Page 437 order, which is the case
+    /// for most DOS and linux console fonts. Only the first 256 glyphs are used.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// fn setup(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    ///     let bytes = std::fs::read("assets/default8x16.psf").unwrap();
+    ///     let font = images.add(TerminalFont::from_psf(&bytes).unwrap());
+    ///     commands.spawn((
+    ///         TerminalBundle::from(Terminal::new([20, 10])),
+    ///         TerminalFont::Custom(font),
+    ///     ));
+    /// }
+    /// ```
+    pub fn from_psf(bytes: &[u8]) -> Result<Image, BitmapFontError> {
+        let (glyph_size, row_bytes, bytes_per_glyph, glyph_count, data) = match bytes {
+            // PSF1 - glyphs are always 8 pixels wide
+            [0x36, 0x04, mode, height, data @ ..] => {
+                let count = if mode & 0x01 != 0 { 512 } else { 256 };
+                let height = *height as u32;
+                (UVec2::new(8, height), 1, height as usize, count, data)
+            }
+            [0x72, 0xb5, 0x4a, 0x86, ..] => {
+                let header = |i: usize| -> Result<u32, BitmapFontError> {
+                    let b = bytes
+                        .get(i * 4..i * 4 + 4)
+                        .ok_or(BitmapFontError::Truncated)?;
+                    Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                };
+                let header_size = header(2)? as usize;
+                let count = header(4)? as usize;
+                let bytes_per_glyph = header(5)? as usize;
+                let size = UVec2::new(header(7)?, header(6)?);
+                let data = bytes.get(header_size..).ok_or(BitmapFontError::Truncated)?;
+                let row_bytes = (size.x as usize).div_ceil(8);
+                (size, row_bytes, bytes_per_glyph, count, data)
+            }
+            _ => return Err(BitmapFontError::UnknownFormat),
+        };
+
+        let mut atlas = Atlas::new(glyph_size);
+        for index in 0..glyph_count.min(256) {
+            let glyph = data
+                .get(index * bytes_per_glyph..(index + 1) * bytes_per_glyph)
+                .ok_or(BitmapFontError::Truncated)?;
+            for y in 0..glyph_size.y {
+                let row = glyph
+                    .get(y as usize * row_bytes..(y as usize + 1) * row_bytes)
+                    .ok_or(BitmapFontError::Truncated)?;
+                for x in 0..glyph_size.x {
+                    if row_bit(row, x) {
+                        atlas.set(index, x, y);
+                    }
+                }
+            }
+        }
+        Ok(atlas.into_image())
+    }
+
+    /// Rasterize a monospace BDF font into a font atlas.
+    ///
+    /// Glyphs are placed according to their encoding, translated to Code
+    /// Page 437. Glyphs which have no Code Page 437 equivalent are skipped.
+    pub fn from_bdf(bytes: &[u8]) -> Result<Image, BitmapFontError> {
+        let invalid = |err: &str| BitmapFontError::InvalidBdf(err.to_string());
+        let text = std::str::from_utf8(bytes).map_err(|_| invalid("Font is not valid utf8"))?;
+
+        let mut lines = text.lines().map(str::trim);
+        let mut atlas = None;
+        // Font bounding box [width, height, x offset, y offset]
+        let mut font_bb = [0; 4];
+
+        while let Some(line) = lines.next() {
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("FONTBOUNDINGBOX") => {
+                    font_bb = parse_ints(words).ok_or_else(|| invalid("Bad FONTBOUNDINGBOX"))?;
+                    if font_bb[0] <= 0 || font_bb[1] <= 0 {
+                        return Err(invalid("Bad FONTBOUNDINGBOX"));
+                    }
+                    let size = UVec2::new(font_bb[0] as u32, font_bb[1] as u32);
+                    atlas = Some(Atlas::new(size));
+                }
+                Some("STARTCHAR") => {
+                    let atlas = atlas
+                        .as_mut()
+                        .ok_or_else(|| invalid("STARTCHAR before FONTBOUNDINGBOX"))?;
+                    let mut encoding = None;
+                    // Glyph bounding box [width, height, x offset, y offset]
+                    let mut bb = font_bb;
+                    for line in lines.by_ref() {
+                        let mut words = line.split_whitespace();
+                        match words.next() {
+                            Some("ENCODING") => {
+                                encoding = words.next().and_then(|w| w.parse::<i32>().ok());
+                            }
+                            Some("BBX") => {
+                                bb = parse_ints(words).ok_or_else(|| invalid("Bad BBX"))?;
+                            }
+                            Some("BITMAP") => break,
+                            _ => {}
+                        }
+                    }
+
+                    let index = encoding.and_then(|e| u32::try_from(e).ok()).and_then(|e| {
+                        if e < 128 {
+                            return Some(e as usize);
+                        }
+                        let ch = char::from_u32(e)?;
+                        match code_page_437::glyph_to_index(ch) {
+                            0 => None,
+                            i => Some(i as usize),
+                        }
+                    });
+
+                    // Row of the glyph's top line within the cell, measured from the top
+                    let top = (font_bb[1] + font_bb[3]) - (bb[1] + bb[3]);
+                    let left = bb[2] - font_bb[2];
+                    for (y, line) in lines.by_ref().enumerate() {
+                        if line == "ENDCHAR" {
+                            break;
+                        }
+                        let Some(index) = index else {
+                            continue;
+                        };
+                        let row = parse_hex_row(line).ok_or_else(|| invalid("Bad BITMAP row"))?;
+                        for x in 0..bb[0] {
+                            let (px, py) = (left + x, top + y as i32);
+                            if row_bit(&row, x as u32) && px >= 0 && py >= 0 {
+                                atlas.set(index, px as u32, py as u32);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        atlas
+            .map(Atlas::into_image)
+            .ok_or_else(|| invalid("Missing FONTBOUNDINGBOX"))
+    }
+}
+
+/// A 16x16 grid of glyph cells.
+struct Atlas {
+    cell: UVec2,
+    data: Vec<u8>,
+}
+
+impl Atlas {
+    fn new(cell: UVec2) -> Self {
+        let size = cell * 16;
+        Self {
+            cell,
+            data: vec![0; (size.x * size.y * 4) as usize],
+        }
+    }
+
+    /// Set a pixel of the glyph at the given index. The glyph's pixels are
+    /// measured from the top left of its cell.
+    fn set(&mut self, index: usize, x: u32, y: u32) {
+        if x >= self.cell.x || y >= self.cell.y {
+            return;
+        }
+        let width = self.cell.x * 16;
+        let cell = UVec2::new(index as u32 % 16, index as u32 / 16) * self.cell;
+        let i = (((cell.y + y) * width + cell.x + x) * 4) as usize;
+        self.data[i..i + 4].copy_from_slice(&[255; 4]);
+    }
+
+    fn into_image(self) -> Image {
+        let size = self.cell * 16;
+        let mut image = Image::new(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            self.data,
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::default(),
+        );
+        image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor::nearest());
+        image
+    }
+}
+
+/// Whether the given pixel is set in a row of packed bits, most significant
+/// bit first.
+fn row_bit(row: &[u8], x: u32) -> bool {
+    row.get(x as usize / 8)
+        .is_some_and(|b| b & (0x80 >> (x % 8)) != 0)
+}
+
+fn parse_hex_row(line: &str) -> Option<Vec<u8>> {
+    (0..line.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(line.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn parse_ints<'a>(mut words: impl Iterator<Item = &'a str>) -> Option<[i32; 4]> {
+    let mut ints = [0; 4];
+    for i in ints.iter_mut() {
+        *i = words.next()?.parse().ok()?;
+    }
+    Some(ints)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(image: &Image, x: u32, y: u32) -> u8 {
+        let width = image.width();
+        image.data[((y * width + x) * 4) as usize]
+    }
+
+    #[test]
+    fn psf1() {
+        let mut bytes = vec![0x36, 0x04, 0x00, 4];
+        bytes.extend(std::iter::repeat_n(0, 256 * 4));
+        // Top left pixel of 'A'
+        bytes[4 + 'A' as usize * 4] = 0x80;
+
+        let image = TerminalFont::from_psf(&bytes).unwrap();
+        assert_eq!(UVec2::new(8 * 16, 4 * 16), image.size());
+
+        // 'A' is index 65, cell [1, 4]
+        assert_eq!(255, pixel(&image, 8, 16));
+        assert_eq!(0, pixel(&image, 9, 16));
+    }
+
+    #[test]
+    fn psf2() {
+        let header = [
+            0x864ab572u32,
+            0,
+            32,
+            0,
+            256,
+            // 10 pixels wide = 2 bytes per row, 3 rows
+            6,
+            3,
+            10,
+        ];
+        let mut bytes: Vec<u8> = header.iter().flat_map(|v| v.to_le_bytes()).collect();
+        bytes.extend(std::iter::repeat_n(0, 256 * 6));
+        // Last pixel in the bottom row of glyph 1
+        bytes[32 + 6 + 5] = 0x40;
+
+        let image = TerminalFont::from_psf(&bytes).unwrap();
+        assert_eq!(UVec2::new(10 * 16, 3 * 16), image.size());
+        assert_eq!(255, pixel(&image, 19, 2));
+    }
+
+    #[test]
+    fn psf_truncated() {
+        let bytes = [0x36, 0x04, 0x00, 8, 0, 0];
+        assert_eq!(
+            Err(BitmapFontError::Truncated),
+            TerminalFont::from_psf(&bytes).map(|_| ())
+        );
+        assert_eq!(
+            Err(BitmapFontError::UnknownFormat),
+            TerminalFont::from_psf(&[1, 2, 3]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn bdf() {
+        let bdf = "STARTFONT 2.1
+            FONTBOUNDINGBOX 6 8 0 -2
+            CHARS 2
+            STARTCHAR A
+            ENCODING 65
+            BBX 4 2 1 0
+            BITMAP
+            80
+            10
+            ENDCHAR
+            STARTCHAR shade
+            ENCODING 9617
+            BBX 6 8 0 -2
+            BITMAP
+            80
+            ENDCHAR
+            ENDFONT";
+
+        let image = TerminalFont::from_bdf(bdf.as_bytes()).unwrap();
+        assert_eq!(UVec2::new(6 * 16, 8 * 16), image.size());
+
+        // 'A' cell is [1, 4], glyph sits on the baseline two rows from the bottom
+        let (cx, cy) = (6, 4 * 8);
+        assert_eq!(255, pixel(&image, cx + 1, cy + 4));
+        assert_eq!(255, pixel(&image, cx + 4, cy + 5));
+
+        // '░' is index 176, cell [0, 11]
+        assert_eq!(255, pixel(&image, 0, 11 * 8));
+    }
+}
@@ -3,15 +3,17 @@
 use bevy::{
     prelude::{
         Added, Assets, BuildChildren, Changed, Children, Commands, Component, Entity, Handle,
-        IVec2, IntoSystemConfigs, Last, Plugin, PostUpdate, Query, Res, Vec2,
+        IVec2, IntoSystemConfigs, Last, Or, Plugin, PostUpdate, Query, RemovedComponents, Res,
+        Transform, Vec2, With,
     },
     utils::HashMap,
 };
 use sark_grids::GridPoint;
 
-use crate::{Edge, TerminalLayout, Tile};
+use crate::{Border, Edge, TerminalLayout, Tile};
 
 use super::{
+    entity::TerminalScrollOffset,
     mesh_data::{TileData, UvMesher, VertData, VertMesher},
     uv_mapping::UvMapping,
     TerminalInit, TerminalRender, TerminalRenderBundle, TerminalUpdateTiles,
@@ -85,9 +87,9 @@ fn update(
                 //if layout.
 
                 mesh.clear();
-                if !layout.has_border() {
-                    return;
-                }
+                let Some(border) = layout.border() else {
+                    continue;
+                };
                 //println!("FOUND BORDER. Inserting border tiles");
 
                 mesh.size = layout.term_size().as_ivec2() + 2;
@@ -96,59 +98,65 @@ fn update(
                 let w = mesh.size.x - 1;
                 let h = mesh.size.y - 1;
 
-                let tile = get_tile(Edge::BottomLeft, layout);
+                let tile = get_tile(Edge::BottomLeft, border, layout);
                 mesh.put_tile([0, 0], tile);
 
-                let tile = get_tile(Edge::TopLeft, layout);
+                let tile = get_tile(Edge::TopLeft, border, layout);
                 mesh.put_tile([0, h], tile);
 
-                let tile = get_tile(Edge::TopRight, layout);
+                let tile = get_tile(Edge::TopRight, border, layout);
                 mesh.put_tile([w, h], tile);
 
-                let tile = get_tile(Edge::BottomRight, layout);
+                let tile = get_tile(Edge::BottomRight, border, layout);
                 mesh.put_tile([w, 0], tile);
 
-                let top = get_tile(Edge::Top, layout);
-                let bot = get_tile(Edge::Bottom, layout);
+                let top = get_tile(Edge::Top, border, layout);
+                let bot = get_tile(Edge::Bottom, border, layout);
                 for x in 1..w {
                     mesh.put_tile([x, h], top);
                     mesh.put_tile([x, 0], bot);
                 }
-                let left = get_tile(Edge::Left, layout);
-                let right = get_tile(Edge::Right, layout);
+                let left = get_tile(Edge::Left, border, layout);
+                let right = get_tile(Edge::Right, border, layout);
                 for y in 1..h {
                     mesh.put_tile([0, y], left);
                     mesh.put_tile([w, y], right);
                 }
 
-                let border = layout.border().unwrap();
-
-                for (edge, aligned_string) in border.edge_strings.iter() {
+                for (edge, strings) in border.edge_strings.iter() {
                     match edge {
                         Edge::Top => {
-                            let align = aligned_string.align;
-                            let string = &aligned_string.string;
-                            let w = mesh.size.x - 2;
-                            let len = string.chars().count();
-                            let x = (align * w as f32).round() as i32;
-                            let x = x - (len as f32 * align).round() as i32;
-
-                            for (i, ch) in string.chars().enumerate() {
-                                let i = i as i32 + 1;
-                                let x = x + i;
-                                let mut tile = mesh.clear_tile;
-                                tile.glyph = ch;
-                                if let Some(col) = aligned_string.fg_col {
-                                    tile.fg_color = col;
+                            // Tile ranges taken by strings already on this
+                            // edge, overlapping strings are skipped
+                            let mut placed: Vec<(i32, i32)> = Vec::new();
+                            for aligned_string in strings {
+                                let align = aligned_string.align;
+                                let string = &aligned_string.string;
+                                let w = mesh.size.x - 2;
+                                let len = string.chars().count();
+                                let x = (align * w as f32).round() as i32;
+                                let x = x - (len as f32 * align).round() as i32;
+
+                                let range = (x + 1, x + len as i32);
+                                if placed.iter().any(|(a, b)| range.0 <= *b && *a <= range.1) {
+                                    continue;
                                 }
-                                if let Some(col) = aligned_string.bg_col {
-                                    tile.bg_color = col;
+                                placed.push(range);
+
+                                for (i, ch) in string.chars().enumerate() {
+                                    let i = i as i32 + 1;
+                                    let x = x + i;
+                                    let mut tile = base_tile(border, layout);
+                                    tile.glyph = ch;
+                                    if let Some(col) = aligned_string.fg_col {
+                                        tile.fg_color = col;
+                                    }
+                                    if let Some(col) = aligned_string.bg_col {
+                                        tile.bg_color = col;
+                                    }
+                                    mesh.put_tile([x, h], tile);
                                 }
-                                mesh.put_tile([x, h], tile);
                             }
-
-                            //let mut tile =
-                            //mesh.put_tile(xy, tile)
                         }
                         Edge::Left => todo!(),
                         Edge::Right => todo!(),
@@ -164,6 +172,35 @@ fn update(
     }
 }
 
+/// Move the border along with the terminal's [`TerminalScrollOffset`]. The
+/// border is a child of the terminal so it's moved by its transform rather
+/// than by rebuilding its mesh.
+#[allow(clippy::type_complexity)]
+fn update_scroll_offset(
+    q_term: Query<
+        (&TerminalLayout, Option<&TerminalScrollOffset>, &Children),
+        Or<(Changed<TerminalLayout>, Changed<TerminalScrollOffset>)>,
+    >,
+    q_children: Query<&Children>,
+    mut removed: RemovedComponents<TerminalScrollOffset>,
+    mut q_border: Query<&mut Transform, With<BorderMesh>>,
+) {
+    let moved = q_term.iter().map(|(layout, scroll, children)| {
+        let offset = scroll.map_or(Vec2::ZERO, |s| s.0 * layout.tile_size);
+        (offset, children)
+    });
+    let reset = removed
+        .read()
+        .filter_map(|e| q_children.get(e).ok())
+        .map(|children| (Vec2::ZERO, children));
+    for (offset, children) in moved.chain(reset) {
+        let mut borders = q_border.iter_many_mut(children);
+        while let Some(mut transform) = borders.fetch_next() {
+            transform.translation = offset.extend(transform.translation.z);
+        }
+    }
+}
+
 fn update_tile_data(
     mut q_mesh: Query<
         (
@@ -184,8 +221,11 @@ fn update_tile_data(
         }
 
         let origin = -(bmesh.size.as_vec2() / 2.0) * bmesh.tile_size;
+        // The mapping may not be loaded yet
+        let Some(mapping) = mappings.get(mapping) else {
+            continue;
+        };
         //println!("Update border tile data");
-        let mapping = mappings.get(mapping).unwrap();
         let mut vmesher = VertMesher::new(origin, bmesh.tile_size, &mut vd);
         let mut tmesher = UvMesher::new(mapping, &mut td);
 
@@ -200,9 +240,24 @@ fn update_tile_data(
     }
 }
 
-fn get_tile(edge: Edge, layout: &TerminalLayout) -> Tile {
+fn get_tile(edge: Edge, border: &Border, layout: &TerminalLayout) -> Tile {
+    let mut tile = base_tile(border, layout);
+    tile.glyph = border.edge_glyph(edge);
+    if let Some(fg) = border.edge_fg_color {
+        tile.fg_color = fg;
+    }
+    if let Some(bg) = border.edge_bg_color {
+        tile.bg_color = bg;
+    }
+    tile
+}
+
+/// The tile all border tiles are built from.
+fn base_tile(border: &Border, layout: &TerminalLayout) -> Tile {
     let mut tile = layout.clear_tile();
-    tile.glyph = layout.border().unwrap().edge_glyph(edge);
+    if let Some(bg) = border.bg_color {
+        tile.bg_color = bg;
+    }
     tile
 }
 
@@ -213,9 +268,116 @@ impl Plugin for BorderMeshPlugin {
         app.add_systems(PostUpdate, init.in_set(TerminalInit))
             .add_systems(
                 Last,
-                (update, update_tile_data)
+                (update, update_scroll_offset, update_tile_data)
                     .after(TerminalUpdateTiles)
                     .before(TerminalRender),
             );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{App, BuildWorldChildren, Color, Update, Vec3};
+
+    use crate::AlignedStringFormatter;
+
+    use super::*;
+
+    fn border_mesh(border: Border) -> BorderMesh {
+        border_mesh_sized(border, [5, 5])
+    }
+
+    fn border_mesh_sized(border: Border, size: [u32; 2]) -> BorderMesh {
+        let mut app = App::new();
+        app.add_systems(Update, update);
+
+        let mut layout = TerminalLayout::default();
+        layout.set_size(size);
+        layout.set_border(Some(border));
+        let term = app
+            .world
+            .spawn(layout)
+            .with_children(|p| {
+                p.spawn(BorderMesh::default());
+            })
+            .id();
+        app.update();
+
+        let children = app.world.get::<Children>(term).unwrap();
+        let child = children[0];
+        app.world.entity_mut(child).take::<BorderMesh>().unwrap()
+    }
+
+    #[test]
+    fn scroll_offset() {
+        let mut app = App::new();
+        app.add_systems(Update, update_scroll_offset);
+
+        let mut layout = TerminalLayout::default();
+        layout.tile_size = Vec2::new(8.0, 8.0);
+        let mut border = Entity::PLACEHOLDER;
+        let term = app
+            .world
+            .spawn((layout, TerminalScrollOffset(Vec2::new(0.5, -1.0))))
+            .with_children(|p| {
+                border = p.spawn((BorderMesh::default(), Transform::default())).id();
+            })
+            .id();
+        app.update();
+        let translation = |app: &App| app.world.get::<Transform>(border).unwrap().translation;
+        assert_eq!(Vec3::new(4.0, -8.0, 0.0), translation(&app));
+
+        app.world.entity_mut(term).remove::<TerminalScrollOffset>();
+        app.update();
+        assert_eq!(Vec3::ZERO, translation(&app));
+    }
+
+    #[test]
+    fn left_and_right_titles() {
+        let top = |mesh: &BorderMesh| -> String {
+            (1..mesh.size.x - 1)
+                .map(|x| mesh.tiles[&IVec2::new(x, mesh.size.y - 1)].tile.glyph)
+                .collect()
+        };
+
+        let mut border = Border::single_line();
+        border.put_title_left("Items");
+        border.put_title_right("1/3");
+        let mesh = border_mesh_sized(border.clone(), [12, 3]);
+        assert_eq!("Items─────1/3", top(&mesh));
+
+        // The right title would overlap, so it's skipped
+        let mesh = border_mesh_sized(border, [6, 3]);
+        assert_eq!("Items──", top(&mesh));
+    }
+
+    #[test]
+    fn border_bg() {
+        let border = Border::single_line()
+            .with_bg(Color::BLUE)
+            .with_title("Hi".fg_col(Color::RED));
+        let mesh = border_mesh(border);
+        assert!(mesh.tiles.values().any(|t| t.tile.glyph == 'H'));
+        assert!(mesh.tiles.values().all(|t| t.tile.bg_color == Color::BLUE));
+    }
+
+    #[test]
+    fn edge_colors() {
+        let border = Border::single_line()
+            .with_edge_colors(Color::RED, Color::GRAY)
+            .with_title("Hi");
+        let mesh = border_mesh(border);
+
+        let top: Vec<_> = mesh
+            .tiles
+            .values()
+            .filter(|t| t.tile.glyph == '─')
+            .collect();
+        assert!(!top.is_empty());
+        assert!(top.iter().all(|t| t.tile.fg_color == Color::RED));
+        assert!(top.iter().all(|t| t.tile.bg_color == Color::GRAY));
+
+        let title = mesh.tiles.values().find(|t| t.tile.glyph == 'H').unwrap();
+        assert_eq!(Tile::default().fg_color, title.tile.fg_color);
+    }
+}
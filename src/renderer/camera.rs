@@ -5,7 +5,6 @@ use crate::TerminalMaterial;
 
 use super::TerminalLayout;
 
-use bevy::prelude::Added;
 use bevy::prelude::AssetEvent;
 use bevy::prelude::Assets;
 use bevy::prelude::Changed;
@@ -22,6 +21,7 @@ use bevy::prelude::Plugin;
 use bevy::prelude::Query;
 use bevy::prelude::Res;
 use bevy::prelude::Transform;
+use bevy::prelude::UVec2;
 use bevy::prelude::With;
 
 use bevy::prelude::App;
@@ -63,17 +63,18 @@ fn init_camera(
 ) {
     // Found a terminal with an autocamera
     if !q_term.is_empty() {
-        // Camera not set up yet, create one
-        if q_cam.is_empty() {
-            //println!("Spawning auto camera");
-            commands.spawn((TiledCameraBundle::new(), TerminalCamera));
-        } else {
-            // Use the first camera we can find
-            let ecam = q_cam.iter().next().unwrap();
-
+        // Use the first camera we can find
+        match q_cam.iter().next() {
+            // Camera not set up yet, create one
+            None => {
+                //println!("Spawning auto camera");
+                commands.spawn((TiledCameraBundle::new(), TerminalCamera::default()));
+            }
             // Found camera but it's missing our TerminalCamera component
-            if q_term_cam.get(ecam).is_err() {
-                commands.entity(ecam).insert(TerminalCamera);
+            Some(ecam) => {
+                if q_term_cam.get(ecam).is_err() {
+                    commands.entity(ecam).insert(TerminalCamera::default());
+                }
             }
         }
     }
@@ -81,11 +82,14 @@ fn init_camera(
 
 fn update(
     q_terminals: Query<(&TerminalLayout, &Handle<TerminalMaterial>), With<AutoCamera>>,
-    mut q_cam: Query<(&mut TiledCamera, &mut Transform), With<TerminalCamera>>,
+    mut q_cam: Query<(&mut TiledCamera, &mut Transform, &TerminalCamera)>,
     images: Res<Assets<Image>>,
     materials: Res<Assets<TerminalMaterial>>,
 ) {
-    if let Ok((mut cam, mut transform)) = q_cam.get_single_mut() {
+    if let Ok((mut cam, mut transform, term_cam)) = q_cam.get_single_mut() {
+        if term_cam.viewport == TerminalCameraViewport::DontModify {
+            return;
+        }
         //println!("UPDATING CAMERA");
         let mut iter = q_terminals.iter();
 
@@ -108,8 +112,19 @@ fn update(
                 rect.envelope_rect(next.0.bounds_with_border());
             }
 
+            // A zero sized viewport would produce a degenerate projection
+            if rect.size().as_uvec2().cmpeq(UVec2::ZERO).any() {
+                return;
+            }
+
             //println!("Updating camera bounds. Final Rect {}", rect);
-            cam.tile_count = rect.size().as_uvec2();
+            cam.tile_count = match term_cam.viewport {
+                TerminalCameraViewport::TargetResolutionPixels(pixels) => {
+                    (pixels / cam.pixels_per_tile.max(UVec2::ONE)).max(UVec2::ONE)
+                }
+                TerminalCameraViewport::TargetResolutionTiles(tiles) => tiles.max(UVec2::ONE),
+                _ => rect.size().as_uvec2(),
+            };
             let z = transform.translation.z;
             transform.translation = rect.center.as_vec2().extend(z);
         }
@@ -117,7 +132,7 @@ fn update(
 }
 
 fn update_cam_conditions(
-    q_cam_added: Query<Entity, (With<TiledCamera>, Added<TerminalCamera>)>,
+    q_cam_added: Query<Entity, (With<TiledCamera>, Changed<TerminalCamera>)>,
     q_layout_changed: Query<&TerminalLayout, Changed<TerminalLayout>>,
     ev_asset: EventReader<AssetEvent<Image>>,
 ) -> bool {
@@ -126,8 +141,35 @@ fn update_cam_conditions(
 
 /// Will track changes to a terminal and update the viewport so the
 /// entire terminal can be visible.
-#[derive(Default, Debug, Component)]
-struct TerminalCamera;
+///
+/// This is added automatically to the camera used by [`AutoCamera`]
+/// terminals. You can add it to a camera yourself to control how the
+/// viewport is managed.
+#[derive(Default, Debug, Clone, Component)]
+pub struct TerminalCamera {
+    pub viewport: TerminalCameraViewport,
+}
+
+/// How a [`TerminalCamera`] manages its viewport.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalCameraViewport {
+    /// Resize and move the viewport to fit every [`AutoCamera`] terminal.
+    #[default]
+    AutoFit,
+    /// Leave the viewport alone, IE: if you want to control the camera
+    /// yourself.
+    DontModify,
+    /// Keep the viewport at a fixed resolution in pixels, centered on the
+    /// [`AutoCamera`] terminals. The resolution is converted to tiles using
+    /// the terminal font's pixels per tile and is scaled up by the largest
+    /// integer zoom that fits the window, with any remaining space
+    /// letterboxed.
+    TargetResolutionPixels(UVec2),
+    /// Keep the viewport at a fixed resolution in tiles, centered on the
+    /// [`AutoCamera`] terminals. As with [`TerminalCameraViewport::TargetResolutionPixels`]
+    /// the viewport is scaled by an integer zoom and letterboxed.
+    TargetResolutionTiles(UVec2),
+}
 
 pub(crate) struct TerminalCameraPlugin;
 
@@ -142,3 +184,112 @@ impl Plugin for TerminalCameraPlugin {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{prelude::*, transform::TransformPlugin};
+
+    use crate::{renderer::TerminalRendererPlugin, Terminal, TerminalBundle};
+
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            TransformPlugin,
+            HierarchyPlugin,
+        ))
+        .init_asset::<Shader>()
+        .init_asset::<Image>()
+        .init_asset::<Mesh>()
+        .add_plugins((TerminalRendererPlugin, TerminalCameraPlugin));
+        app
+    }
+
+    #[test]
+    fn no_terminals() {
+        let mut app = app();
+        app.world
+            .spawn((TiledCameraBundle::new(), TerminalCamera::default()));
+        app.update();
+        app.update();
+    }
+
+    #[test]
+    fn zero_size_terminal() {
+        let mut app = app();
+        let mut bundle = TiledCameraBundle::new();
+        bundle.tiled_camera.tile_count = UVec2::new(3, 3);
+        let translation = bundle.cam2d.transform.translation;
+        let ecam = app.world.spawn((bundle, TerminalCamera::default())).id();
+
+        // Terminals are never smaller than 1x1, so shrink a layout directly
+        let mut layout = TerminalLayout::default();
+        layout.set_size([-1, -1]);
+        assert_eq!(UVec2::ZERO, layout.term_size());
+        app.world
+            .spawn((layout, Handle::<TerminalMaterial>::default(), AutoCamera));
+        app.update();
+        app.update();
+
+        // The viewport is left alone rather than made degenerate
+        let cam = app.world.get::<TiledCamera>(ecam).unwrap();
+        assert_eq!(UVec2::new(3, 3), cam.tile_count);
+        let transform = app.world.get::<Transform>(ecam).unwrap();
+        assert_eq!(translation, transform.translation);
+    }
+
+    #[test]
+    fn dont_modify_viewport() {
+        let mut app = app();
+        let cam = TerminalCamera {
+            viewport: TerminalCameraViewport::DontModify,
+        };
+        let mut bundle = TiledCameraBundle::new();
+        bundle.tiled_camera.tile_count = UVec2::new(3, 3);
+        let translation = bundle.cam2d.transform.translation;
+        let ecam = app.world.spawn((bundle, cam)).id();
+        let mut term = Terminal::new([10, 5]);
+        term.put_char([0, 0], 'a');
+        app.world.spawn((TerminalBundle::from(term), AutoCamera));
+
+        app.update();
+        app.update();
+
+        let cam = app.world.get::<TiledCamera>(ecam).unwrap();
+        assert_eq!(UVec2::new(3, 3), cam.tile_count);
+        let transform = app.world.get::<Transform>(ecam).unwrap();
+        assert_eq!(translation, transform.translation);
+    }
+
+    fn target_resolution(viewport: TerminalCameraViewport) -> (UVec2, UVec2) {
+        let mut app = app();
+        let ecam = app
+            .world
+            .spawn((TiledCameraBundle::new(), TerminalCamera { viewport }))
+            .id();
+        app.world
+            .spawn((TerminalBundle::from(Terminal::new([10, 5])), AutoCamera));
+        app.update();
+        app.update();
+
+        let cam = app.world.get::<TiledCamera>(ecam).unwrap();
+        (cam.tile_count, cam.pixels_per_tile.max(UVec2::ONE))
+    }
+
+    #[test]
+    fn target_resolution_tiles() {
+        let viewport = TerminalCameraViewport::TargetResolutionTiles(UVec2::new(40, 25));
+        let (tile_count, _) = target_resolution(viewport);
+        assert_eq!(UVec2::new(40, 25), tile_count);
+    }
+
+    #[test]
+    fn target_resolution_pixels() {
+        let viewport = TerminalCameraViewport::TargetResolutionPixels(UVec2::new(320, 200));
+        let (tile_count, ppu) = target_resolution(viewport);
+        assert_eq!(UVec2::new(320, 200) / ppu, tile_count);
+    }
+}
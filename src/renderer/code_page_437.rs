@@ -306,3 +306,120 @@ pub fn glyph_to_index(c: char) -> u8 {
         _ => 0,
     }
 }
+
+/// Returns true if the glyph is part of code page 437.
+///
+/// Unlike [`glyph_to_index`] this can be used in a const context, see
+/// [`glyph!`](crate::glyph).
+pub const fn is_cp437_glyph(c: char) -> bool {
+    let mut i = 0;
+    while i < CP_437_CHARS.len() {
+        if CP_437_CHARS[i] == c {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// A code page 437 glyph, checked at compile time.
+///
+/// Using a glyph that isn't part of code page 437 is a compile error, so
+/// typos in level or ui definitions are caught early. The macro evaluates to
+/// the `char` itself.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ascii_terminal::*;
+///
+/// let mut term = Terminal::new([3, 3]);
+/// term.put_char([1, 1], glyph!('♥'));
+/// assert_eq!('♥', term.get_char([1, 1]));
+/// ```
+///
+/// A glyph that isn't part of code page 437 won't compile:
+///
+/// ```rust compile_fail
+/// use bevy_ascii_terminal::*;
+///
+/// let heart = glyph!('❤');
+/// ```
+#[macro_export]
+macro_rules! glyph {
+    ($glyph:literal) => {{
+        const GLYPH: char = $glyph;
+        const _: () = assert!(
+            $crate::code_page_437::is_cp437_glyph(GLYPH),
+            "glyph! requires a code page 437 glyph"
+        );
+        GLYPH
+    }};
+}
+
+/// Returns true if the glyph is a box drawing glyph, such as `│`, `╗` or `┼`.
+pub fn is_box_drawing(c: char) -> bool {
+    matches!(glyph_to_index(c), 179..=218)
+}
+
+/// Returns true if the glyph is a block glyph, such as `█` or `▄`.
+pub fn is_block(c: char) -> bool {
+    matches!(glyph_to_index(c), 219..=223)
+}
+
+/// Returns true if the glyph is a shade glyph: `░`, `▒` or `▓`.
+pub fn is_shade(c: char) -> bool {
+    matches!(glyph_to_index(c), 176..=178)
+}
+
+/// An iterator over all the box drawing glyphs, in code page 437 order.
+pub fn box_drawing_glyphs() -> impl Iterator<Item = char> {
+    (179..=218).map(index_to_glyph)
+}
+
+/// An iterator over all the block glyphs, in code page 437 order.
+pub fn block_glyphs() -> impl Iterator<Item = char> {
+    (219..=223).map(index_to_glyph)
+}
+
+/// An iterator over all the shade glyphs, from lightest to darkest.
+pub fn shade_glyphs() -> impl Iterator<Item = char> {
+    (176..=178).map(index_to_glyph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cp437_glyphs() {
+        assert!(CP_437_CHARS.iter().all(|c| is_cp437_glyph(*c)));
+        assert!(!is_cp437_glyph('❤'));
+        assert!(!is_cp437_glyph('╭'));
+
+        const HEART: char = crate::glyph!('♥');
+        assert_eq!(3, glyph_to_index(HEART));
+    }
+
+    #[test]
+    fn glyph_categories() {
+        assert!(is_box_drawing('─'));
+        assert!(is_box_drawing('╬'));
+        assert!(!is_box_drawing('█'));
+        assert!(!is_box_drawing('a'));
+
+        assert!(is_block('█'));
+        assert!(!is_block('─'));
+
+        assert!(is_shade('▒'));
+        assert!(!is_shade(' '));
+
+        assert_eq!(40, box_drawing_glyphs().count());
+        assert!(box_drawing_glyphs().all(is_box_drawing));
+        assert_eq!(vec!['░', '▒', '▓'], shade_glyphs().collect::<Vec<_>>());
+        assert_eq!(
+            vec!['█', '▄', '▌', '▐', '▀'],
+            block_glyphs().collect::<Vec<_>>()
+        );
+    }
+}
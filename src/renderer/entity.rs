@@ -1,7 +1,7 @@
 //! Terminal components
 
 use bevy::{
-    prelude::{Bundle, Component, Deref, Handle, UVec2},
+    prelude::{Bundle, Component, Deref, Handle, UVec2, Vec2},
     sprite::MaterialMesh2dBundle,
 };
 
@@ -27,6 +27,15 @@ pub enum TileScaling {
     Pixels,
 }
 
+/// Offsets a terminal's mesh by a fractional number of tiles, IE: for smoothly
+/// scrolling a camera over a large terminal.
+///
+/// The offset is in tiles and only affects rendering, the terminal's tiles
+/// and transform are unchanged. The terminal's border is moved with it, and
+/// [`ToWorld`](crate::ToWorld) accounts for it when converting positions.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct TerminalScrollOffset(pub Vec2);
+
 /// Bundle for a rendering a terminal.
 /// Has various functions to help with the construction of a terminal.
 #[derive(Default, Bundle)]
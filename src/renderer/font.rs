@@ -132,7 +132,7 @@ fn terminal_renderer_change_font(
         // The requested font might still be loading, this is why we remove
         // the TerminalFont component rather than using change detection
         if images.get(handle).is_none() {
-            return;
+            continue;
         }
 
         info!("Changing material");
@@ -15,6 +15,9 @@ use super::TileScaling;
 #[derive(Debug, Component, Clone)]
 pub struct TerminalLayout {
     pub scaling: TileScaling,
+    /// If set, tiles will use this aspect ratio (width / height) instead of
+    /// the one derived from the font image when using [`TileScaling::World`].
+    pub aspect_override: Option<f32>,
     pub(crate) pivot: Pivot,
     //pub(crate) border_entity: Option<Entity>,
     border: Option<Border>,
@@ -30,6 +33,7 @@ impl Default for TerminalLayout {
         Self {
             tile_size: Vec2::ONE,
             scaling: TileScaling::World,
+            aspect_override: None,
             pixels_per_tile: uvec2(8, 8),
             pivot: Pivot::Center,
             border: None,
@@ -55,6 +59,14 @@ impl TerminalLayout {
         self.pixels_per_tile
     }
 
+    /// The size in pixels required to render the terminal, including its
+    /// border, at 1x zoom.
+    ///
+    /// This can be used to size the window to exactly fit the terminal.
+    pub fn target_pixel_size(&self) -> UVec2 {
+        self.bounds_with_border().size().as_uvec2() * self.pixels_per_tile
+    }
+
     pub(crate) fn update_state(&mut self, term: &Terminal, pos: IVec2) {
         if self.border.as_ref() != term.border() {
             self.border = term.border().cloned();
@@ -131,7 +143,7 @@ impl From<&Terminal> for TerminalLayout {
 mod tests {
     use sark_grids::{geometry::GridRect, Pivot};
 
-    use crate::{Terminal, TerminalLayout};
+    use crate::{Border, Terminal, TerminalLayout};
 
     #[test]
     fn bounds() {
@@ -151,4 +163,15 @@ mod tests {
 
         println!("P {}", layout.pivot_pos(Pivot::TopRight));
     }
+
+    #[test]
+    fn target_pixel_size() {
+        let mut term = Terminal::new([80, 25]);
+        let layout = TerminalLayout::from(&term);
+        assert_eq!([640, 200], layout.target_pixel_size().to_array());
+
+        term.set_border(Border::single_line());
+        let layout = TerminalLayout::from(&term);
+        assert_eq!([656, 216], layout.target_pixel_size().to_array());
+    }
 }
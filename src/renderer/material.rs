@@ -7,8 +7,8 @@
 use bevy::{
     math::Vec4,
     prelude::{
-        default, Asset, Assets, Changed, Color, Handle, Image, Mesh, Or, Plugin, Query, Res,
-        Shader, Vec2,
+        default, Asset, AssetEvent, Assets, Color, DetectChanges, EventReader, Handle, Image, Mesh,
+        Plugin, Query, Ref, Res, Shader, Vec2,
     },
     reflect::TypePath,
     render::{
@@ -77,6 +77,15 @@ pub struct TerminalMaterial {
     #[texture(1)]
     #[sampler(2)]
     pub texture: Option<Handle<Image>>,
+    /// An optional second texture for glyphs which aren't present in the main
+    /// texture, such as emoji. Glyphs are mapped to this texture via
+    /// [`UvMapping::with_fallback`](crate::UvMapping::with_fallback).
+    #[texture(3)]
+    #[sampler(4)]
+    pub fallback_texture: Option<Handle<Image>>,
+    /// If set, glyphs will be drawn with a one pixel outline of this color,
+    /// to keep them readable on busy backgrounds.
+    pub glyph_outline: Option<Color>,
 }
 
 impl Default for TerminalMaterial {
@@ -84,6 +93,8 @@ impl Default for TerminalMaterial {
         Self {
             bg_clip_color: Color::BLACK,
             texture: None,
+            fallback_texture: None,
+            glyph_outline: None,
         }
     }
 }
@@ -102,6 +113,8 @@ bitflags::bitflags! {
     #[repr(transparent)]
     pub struct TerminalMaterialFlags: u32 {
         const TEXTURE           = (1 << 0);
+        const FALLBACK_TEXTURE  = (1 << 1);
+        const GLYPH_OUTLINE     = (1 << 2);
         const NONE              = 0;
         const UNINITIALIZED     = 0xFFFF;
     }
@@ -111,6 +124,7 @@ bitflags::bitflags! {
 #[derive(Clone, Default, ShaderType)]
 struct TerminalMaterialUniform {
     pub color: Vec4,
+    pub outline_color: Vec4,
     pub flags: u32,
 }
 
@@ -120,9 +134,20 @@ impl AsBindGroupShaderType<TerminalMaterialUniform> for TerminalMaterial {
         if self.texture.is_some() {
             flags |= TerminalMaterialFlags::TEXTURE;
         }
+        if self.fallback_texture.is_some() {
+            flags |= TerminalMaterialFlags::FALLBACK_TEXTURE;
+        }
+        if self.glyph_outline.is_some() {
+            flags |= TerminalMaterialFlags::GLYPH_OUTLINE;
+        }
 
         TerminalMaterialUniform {
             color: self.bg_clip_color.as_linear_rgba_f32().into(),
+            outline_color: self
+                .glyph_outline
+                .unwrap_or(Color::NONE)
+                .as_linear_rgba_f32()
+                .into(),
             flags: flags.bits(),
         }
     }
@@ -154,33 +179,187 @@ impl Material2d for TerminalMaterial {
     }
 }
 
+/// Update terminal layouts when their material changes.
+///
+/// A terminal's material or font image might not be loaded yet when it's
+/// assigned, so terminals are also re-checked whenever a material or image
+/// finishes loading. Changes to the layout itself, IE: setting
+/// [`TerminalLayout::aspect_override`], are also picked up.
 #[allow(clippy::type_complexity)]
 pub(crate) fn material_change(
     materials: Res<Assets<TerminalMaterial>>,
     images: Res<Assets<Image>>,
-    mut q_term: Query<
-        (&Handle<TerminalMaterial>, &mut TerminalLayout),
-        Or<(Changed<Handle<TerminalMaterial>>, Changed<TerminalFont>)>,
-    >,
+    mut ev_material: EventReader<AssetEvent<TerminalMaterial>>,
+    mut ev_image: EventReader<AssetEvent<Image>>,
+    mut q_term: Query<(
+        Ref<Handle<TerminalMaterial>>,
+        Option<Ref<TerminalFont>>,
+        &mut TerminalLayout,
+    )>,
 ) {
-    for (handle, mut layout) in &mut q_term {
-        if let Some(material) = materials.get(handle) {
-            if let Some(image) = material.texture.clone() {
-                if let Some(image) = images.get(&image) {
-                    // TODO: Should be derived from image size, can't assume 16x16 tilesheet for
-                    // graphical terminals
-                    let font_size = image.size().as_vec2() / 16.0;
-                    layout.pixels_per_tile = font_size.as_uvec2();
-                    layout.tile_size = match layout.scaling {
-                        TileScaling::World => {
-                            let aspect = font_size.x / font_size.y;
-                            Vec2::new(aspect, 1.0)
-                        }
-                        TileScaling::Pixels => font_size,
-                    };
-                    //info!("Updating layout ppt. Now {}", layout.pixels_per_tile);
-                }
+    let assets_changed = ev_material.read().count() > 0 || ev_image.read().count() > 0;
+
+    for (handle, font, mut layout) in &mut q_term {
+        let font_changed = font.is_some_and(|font| font.is_changed());
+        if !assets_changed && !handle.is_changed() && !font_changed && !layout.is_changed() {
+            continue;
+        }
+        // The material or its image may still be loading, in which case
+        // the layout will be updated once it's ready
+        let Some(material) = materials.get(&*handle) else {
+            continue;
+        };
+        let Some(image) = material.texture.as_ref().and_then(|i| images.get(i)) else {
+            continue;
+        };
+        // TODO: Should be derived from image size, can't assume 16x16 tilesheet for
+        // graphical terminals
+        let font_size = image.size().as_vec2() / 16.0;
+        let pixels_per_tile = font_size.as_uvec2();
+        let tile_size = match layout.scaling {
+            TileScaling::World => {
+                let aspect = layout.aspect_override.unwrap_or(font_size.x / font_size.y);
+                Vec2::new(aspect, 1.0)
             }
+            TileScaling::Pixels => font_size,
+        };
+        // Avoid triggering a rebuild of the terminal mesh if nothing changed
+        if layout.pixels_per_tile != pixels_per_tile || layout.tile_size != tile_size {
+            layout.pixels_per_tile = pixels_per_tile;
+            layout.tile_size = tile_size;
         }
+        //info!("Updating layout ppt. Now {}", layout.pixels_per_tile);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        prelude::{App, AssetApp, AssetPlugin, Update},
+        render::{
+            render_asset::RenderAssetUsages,
+            render_resource::{Extent3d, TextureDimension, TextureFormat},
+        },
+        MinimalPlugins,
+    };
+
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_asset::<TerminalMaterial>()
+            .add_systems(Update, material_change);
+        app
+    }
+
+    /// A font image with 10x5 pixel tiles
+    fn font_image() -> Image {
+        Image::new_fill(
+            Extent3d {
+                width: 160,
+                height: 80,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    fn tile_size(aspect_override: Option<f32>) -> Vec2 {
+        let mut app = app();
+        let image = app.world.resource_mut::<Assets<Image>>().add(font_image());
+        let material = app
+            .world
+            .resource_mut::<Assets<TerminalMaterial>>()
+            .add(TerminalMaterial::from(image));
+
+        let mut layout = TerminalLayout::default();
+        layout.aspect_override = aspect_override;
+        let e = app.world.spawn((layout, material)).id();
+        app.update();
+
+        app.world.get::<TerminalLayout>(e).unwrap().tile_size
+    }
+
+    #[test]
+    fn aspect_override() {
+        assert_eq!(Vec2::new(2.0, 1.0), tile_size(None));
+        assert_eq!(Vec2::new(1.0, 1.0), tile_size(Some(1.0)));
+    }
+
+    #[test]
+    fn aspect_override_after_spawn() {
+        let mut app = app();
+        let image = app.world.resource_mut::<Assets<Image>>().add(font_image());
+        let material = app
+            .world
+            .resource_mut::<Assets<TerminalMaterial>>()
+            .add(TerminalMaterial::from(image));
+        let e = app.world.spawn((TerminalLayout::default(), material)).id();
+        app.update();
+        let layout = app.world.get::<TerminalLayout>(e).unwrap();
+        assert_eq!(Vec2::new(2.0, 1.0), layout.tile_size);
+
+        app.world
+            .get_mut::<TerminalLayout>(e)
+            .unwrap()
+            .aspect_override = Some(0.5);
+        app.update();
+        let layout = app.world.get::<TerminalLayout>(e).unwrap();
+        assert_eq!(Vec2::new(0.5, 1.0), layout.tile_size);
+    }
+
+    #[test]
+    fn material_loaded_late() {
+        let mut app = app();
+        let material = app
+            .world
+            .resource::<Assets<TerminalMaterial>>()
+            .reserve_handle();
+        let e = app
+            .world
+            .spawn((TerminalLayout::default(), material.clone()))
+            .id();
+
+        // Material isn't loaded yet
+        app.update();
+        let layout = app.world.get::<TerminalLayout>(e).unwrap();
+        assert_eq!(Vec2::ONE, layout.tile_size);
+
+        let image = app.world.resource_mut::<Assets<Image>>().add(font_image());
+        app.world
+            .resource_mut::<Assets<TerminalMaterial>>()
+            .insert(&material, TerminalMaterial::from(image));
+        app.update();
+        app.update();
+
+        let layout = app.world.get::<TerminalLayout>(e).unwrap();
+        assert_eq!(Vec2::new(2.0, 1.0), layout.tile_size);
+    }
+
+    #[test]
+    fn glyph_outline_uniform() {
+        let images = RenderAssets::<Image>::default();
+        let material = TerminalMaterial::default();
+        let uniform: TerminalMaterialUniform = material.as_bind_group_shader_type(&images);
+        assert_eq!(
+            0,
+            uniform.flags & TerminalMaterialFlags::GLYPH_OUTLINE.bits()
+        );
+
+        let material = TerminalMaterial {
+            glyph_outline: Some(Color::RED),
+            ..default()
+        };
+        let uniform: TerminalMaterialUniform = material.as_bind_group_shader_type(&images);
+        assert_ne!(
+            0,
+            uniform.flags & TerminalMaterialFlags::GLYPH_OUTLINE.bits()
+        );
+        assert_eq!(Vec4::new(1.0, 0.0, 0.0, 1.0), uniform.outline_color);
     }
 }
@@ -1,7 +1,10 @@
 use bevy::{
     ecs::prelude::*,
     prelude::{Assets, Mesh},
-    render::{mesh::Indices, render_asset::RenderAssetUsages, render_resource::PrimitiveTopology},
+    render::{
+        mesh::Indices, primitives::Aabb, render_asset::RenderAssetUsages,
+        render_resource::PrimitiveTopology,
+    },
     sprite::Mesh2dHandle,
 };
 
@@ -26,14 +29,29 @@ pub(crate) fn init_mesh(
     }
 }
 
+/// Write vertex data to the terminal mesh.
+///
+/// This also keeps the terminal's [`Aabb`] in sync with the mesh, otherwise
+/// it would only be calculated once and off-screen terminals wouldn't be
+/// culled correctly.
 pub(crate) fn update_mesh_verts(
-    mut q_mesh: Query<(&mut VertData, &Mesh2dHandle), Changed<VertData>>,
+    mut q_mesh: Query<(Entity, &mut VertData, &Mesh2dHandle, Option<&mut Aabb>), Changed<VertData>>,
     mut meshes: ResMut<Assets<Mesh>>,
+    mut commands: Commands,
 ) {
-    for (mut vd, handle) in &mut q_mesh {
+    for (entity, mut vd, handle, aabb) in &mut q_mesh {
         if let Some(mesh) = meshes.get_mut(&handle.0) {
             vd.build_mesh_verts(mesh);
             //println!("Updating mesh verts. Indices count {}", mesh.indices().unwrap().len());
+            let Some(new_aabb) = mesh.compute_aabb() else {
+                continue;
+            };
+            match aabb {
+                Some(mut aabb) => *aabb = new_aabb,
+                None => {
+                    commands.entity(entity).insert(new_aabb);
+                }
+            }
         }
     }
 }
@@ -33,24 +33,26 @@ impl VertData {
         self.indices.reserve(tile_count * 6);
     }
 
+    /// Move the vertex data into the mesh.
+    ///
+    /// If the mesh is missing the expected attributes (IE: it was replaced
+    /// or hot-reloaded) they will be re-inserted rather than panicking.
     pub fn build_mesh_verts(&mut self, mesh: &mut Mesh) {
-        let verts = mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION).unwrap();
-        let verts = match verts {
-            VertexAttributeValues::Float32x3(verts) => verts,
-            _ => panic!("Unexpected vertex position format"),
-        };
-        verts.clear();
-        verts.append(&mut self.verts);
-
-        let indices = mesh
-            .indices_mut()
-            .expect("Error retrieving terminal mesh indices");
-        let indices = match indices {
-            Indices::U32(indices) => indices,
-            _ => panic!("Terminal indices are u16, expected u32"),
-        };
-        indices.clear();
-        indices.append(&mut self.indices);
+        match mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(verts)) => {
+                verts.clear();
+                verts.append(&mut self.verts);
+            }
+            _ => mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, std::mem::take(&mut self.verts)),
+        }
+
+        match mesh.indices_mut() {
+            Some(Indices::U32(indices)) => {
+                indices.clear();
+                indices.append(&mut self.indices);
+            }
+            _ => mesh.insert_indices(Indices::U32(std::mem::take(&mut self.indices))),
+        }
     }
 }
 
@@ -74,36 +76,34 @@ impl TileData {
         self.bg.reserve(tile_count * 4);
     }
 
+    /// Move the tile data into the mesh.
+    ///
+    /// If the mesh is missing the expected attributes (IE: it was replaced
+    /// or hot-reloaded) they will be re-inserted rather than panicking.
     pub fn build_mesh_tiles(&mut self, mesh: &mut Mesh) {
-        let uvs = mesh
-            .attribute_mut(ATTRIBUTE_UV)
-            .expect("Error retrieving mesh uv data");
-        let uvs = match uvs {
-            VertexAttributeValues::Float32x2(uvs) => uvs,
-            _ => panic!("Unexpected uv format"),
-        };
-        uvs.clear();
-        uvs.append(&mut self.uvs);
-
-        let fg_cols = mesh
-            .attribute_mut(ATTRIBUTE_COLOR_FG)
-            .expect("Error retrieving terminal mesh fg colors");
-        let fg_cols = match fg_cols {
-            VertexAttributeValues::Float32x4(fg) => fg,
-            _ => panic!("Unexpected fg colors format"),
-        };
-        fg_cols.clear();
-        fg_cols.append(&mut self.fg);
-
-        let bg_cols = mesh
-            .attribute_mut(ATTRIBUTE_COLOR_BG)
-            .expect("Error retrieving terminal mesh bg colors");
-        let bg_cols = match bg_cols {
-            VertexAttributeValues::Float32x4(bg) => bg,
-            _ => panic!("Unexpected bg colors format"),
-        };
-        bg_cols.clear();
-        bg_cols.append(&mut self.bg);
+        match mesh.attribute_mut(ATTRIBUTE_UV) {
+            Some(VertexAttributeValues::Float32x2(uvs)) => {
+                uvs.clear();
+                uvs.append(&mut self.uvs);
+            }
+            _ => mesh.insert_attribute(ATTRIBUTE_UV, std::mem::take(&mut self.uvs)),
+        }
+
+        match mesh.attribute_mut(ATTRIBUTE_COLOR_FG) {
+            Some(VertexAttributeValues::Float32x4(fg)) => {
+                fg.clear();
+                fg.append(&mut self.fg);
+            }
+            _ => mesh.insert_attribute(ATTRIBUTE_COLOR_FG, std::mem::take(&mut self.fg)),
+        }
+
+        match mesh.attribute_mut(ATTRIBUTE_COLOR_BG) {
+            Some(VertexAttributeValues::Float32x4(bg)) => {
+                bg.clear();
+                bg.append(&mut self.bg);
+            }
+            _ => mesh.insert_attribute(ATTRIBUTE_COLOR_BG, std::mem::take(&mut self.bg)),
+        }
     }
 }
 
@@ -154,22 +154,46 @@ impl<'a> UvMesher<'a> {
 
     /// Generate tile uvs for the next tile. Note these are not positional,
     /// they must be added in the same order as the vert data.
+    ///
+    /// Glyphs that aren't present in the mapping are drawn as a space.
     pub fn tile_uvs(&mut self, glyph: char, fg: Color, bg: Color) {
+        let glyph_uv = self.glyph_uvs(glyph);
         let td = &mut self.tile_data;
-        let glyph_uv = self.mapping.uvs_from_glyph(glyph);
         td.uvs.extend(glyph_uv);
         td.fg
             .extend(std::iter::repeat(fg.as_linear_rgba_f32()).take(4));
         td.bg
             .extend(std::iter::repeat(bg.as_linear_rgba_f32()).take(4));
     }
+
+    /// Generate uvs for the next `count` tiles, which all share the same
+    /// glyph and colors. This is faster than calling
+    /// [`UvMesher::tile_uvs`] for each tile, IE: for a cleared terminal. See
+    /// `benches/clear.rs`.
+    pub fn fill_uvs(&mut self, count: usize, glyph: char, fg: Color, bg: Color) {
+        let glyph_uv = self.glyph_uvs(glyph);
+        let td = &mut self.tile_data;
+        td.uvs.extend(glyph_uv.iter().cycle().take(count * 4));
+        td.fg
+            .resize(td.fg.len() + count * 4, fg.as_linear_rgba_f32());
+        td.bg
+            .resize(td.bg.len() + count * 4, bg.as_linear_rgba_f32());
+    }
+
+    fn glyph_uvs(&self, glyph: char) -> [[f32; 2]; 4] {
+        self.mapping
+            .get_uvs(glyph)
+            .or_else(|| self.mapping.get_uvs(' '))
+            .copied()
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use bevy::prelude::Color;
 
-    use crate::renderer::uv_mapping::UvMapping;
+    use crate::{renderer::uv_mapping::UvMapping, Tile};
 
     use super::*;
 
@@ -193,4 +217,67 @@ mod test {
         assert_eq!(4, td.fg.len());
         assert_eq!(4, td.bg.len());
     }
+
+    #[test]
+    fn transparent_bg() {
+        let mapping = UvMapping::default();
+        let mut td = TileData::default();
+        let mut mesher = UvMesher::new(&mapping, &mut td);
+
+        let tile = Tile::transparent();
+        mesher.tile_uvs(tile.glyph, tile.fg_color, tile.bg_color);
+
+        assert!(td.bg.iter().all(|bg| bg[3] == 0.0));
+    }
+
+    #[test]
+    fn fill_uvs() {
+        let mapping = UvMapping::default();
+        let mut per_tile = TileData::default();
+        let mut mesher = UvMesher::new(&mapping, &mut per_tile);
+        for _ in 0..6 {
+            mesher.tile_uvs('a', Color::BLUE, Color::YELLOW);
+        }
+
+        let mut bulk = TileData::default();
+        let mut mesher = UvMesher::new(&mapping, &mut bulk);
+        mesher.fill_uvs(6, 'a', Color::BLUE, Color::YELLOW);
+
+        assert_eq!(per_tile.uvs, bulk.uvs);
+        assert_eq!(per_tile.fg, bulk.fg);
+        assert_eq!(per_tile.bg, bulk.bg);
+    }
+
+    #[test]
+    fn unmapped_glyph() {
+        let mapping = UvMapping::default();
+        let mut td = TileData::default();
+        let mut mesher = UvMesher::new(&mapping, &mut td);
+
+        mesher.tile_uvs('€', Color::WHITE, Color::BLACK);
+
+        assert_eq!(mapping.uvs_from_glyph(' '), &td.uvs[..4]);
+    }
+
+    #[test]
+    fn rebuild_missing_attributes() {
+        let mut mesh = Mesh::new(
+            bevy::render::render_resource::PrimitiveTopology::TriangleList,
+            bevy::render::render_asset::RenderAssetUsages::default(),
+        );
+
+        let mut vd = VertData::default();
+        VertMesher::new([0, 0], [1.0, 1.0], &mut vd).tile_verts_at([0, 0]);
+        vd.build_mesh_verts(&mut mesh);
+
+        let mapping = UvMapping::default();
+        let mut td = TileData::default();
+        UvMesher::new(&mapping, &mut td).tile_uvs('a', Color::WHITE, Color::BLACK);
+        td.build_mesh_tiles(&mut mesh);
+
+        assert_eq!(4, mesh.count_vertices());
+        assert_eq!(6, mesh.indices().unwrap().len());
+        assert_eq!(4, mesh.attribute(ATTRIBUTE_UV).unwrap().len());
+        assert_eq!(4, mesh.attribute(ATTRIBUTE_COLOR_BG).unwrap().len());
+    }
 }
@@ -1,5 +1,6 @@
 //! Handles mesh construction and rendering for the terminal.
 
+mod batch;
 mod border_mesh;
 mod entity;
 mod layout;
@@ -14,20 +15,32 @@ mod uv_mapping;
 #[cfg(feature = "camera")]
 mod camera;
 
+#[cfg(feature = "bitmap_fonts")]
+mod bitmap_font;
+
 pub mod code_page_437;
 
 use bevy::prelude::{App, IntoSystemConfigs, Last, Plugin, SystemSet};
 pub(crate) use font::BuiltInFontHandles;
 
+pub use batch::{TerminalBatch, TerminalBatchBundle};
 pub use entity::*;
 
 pub use font::TerminalFont;
 
+#[cfg(feature = "bitmap_fonts")]
+pub use bitmap_font::BitmapFontError;
+
 pub use layout::TerminalLayout;
 pub use material::TerminalMaterial;
+pub use uv_mapping::UvMapping;
 
 #[cfg(feature = "camera")]
-pub use camera::{AutoCamera, TiledCamera, TiledCameraBundle};
+pub(crate) use camera::TerminalCameraPlugin;
+#[cfg(feature = "camera")]
+pub use camera::{
+    AutoCamera, TerminalCamera, TerminalCameraViewport, TiledCamera, TiledCameraBundle,
+};
 
 /// System set for the terminal mesh initialization system.
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
@@ -72,6 +85,7 @@ impl Plugin for TerminalRendererPlugin {
                 terminal_mesh::update_layout.in_set(TerminalLayoutChange),
                 terminal_mesh::update_vert_data.in_set(TerminalUpdateTiles),
                 terminal_mesh::update_tile_data.in_set(TerminalUpdateTiles),
+                batch::update_batch.in_set(TerminalUpdateTiles),
                 mesh::update_mesh_verts.in_set(TerminalRender),
                 mesh::update_mesh_tiles.in_set(TerminalRender),
             )
@@ -79,10 +93,158 @@ impl Plugin for TerminalRendererPlugin {
         );
         app.add_plugins((
             material::TerminalMaterialPlugin,
-            #[cfg(feature = "camera")]
-            camera::TerminalCameraPlugin,
             uv_mapping::UvMappingPlugin,
             border_mesh::BorderMeshPlugin,
         ));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        math::Vec3A,
+        prelude::*,
+        render::{
+            deterministic::DeterministicRenderingConfig, mesh::VertexAttributeValues,
+            primitives::Aabb, view::VisibilityPlugin,
+        },
+        sprite::Mesh2dHandle,
+        transform::TransformPlugin,
+    };
+
+    use crate::{Terminal, TerminalBundle, TileFormatter};
+
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            TransformPlugin,
+            HierarchyPlugin,
+        ))
+        .init_asset::<Shader>()
+        .init_asset::<Image>()
+        .init_asset::<Mesh>()
+        .init_resource::<DeterministicRenderingConfig>()
+        .add_plugins((VisibilityPlugin, TerminalRendererPlugin));
+        app
+    }
+
+    #[test]
+    fn render_with_camera_2d() {
+        let mut app = app();
+
+        app.world.spawn(Camera2dBundle::default());
+        let mut term = Terminal::new([10, 3]);
+        term.put_string([0, 0], "Hello");
+        let e = app
+            .world
+            .spawn(TerminalBundle::from(term).with_tile_scaling(TileScaling::Pixels))
+            .id();
+
+        app.update();
+        app.update();
+
+        let layout = app.world.get::<TerminalLayout>(e).unwrap();
+        assert_eq!(Vec2::new(8.0, 8.0), layout.tile_size);
+
+        let handle = app.world.get::<Mesh2dHandle>(e).unwrap();
+        let meshes = app.world.resource::<Assets<Mesh>>();
+        let mesh = meshes.get(&handle.0).unwrap();
+        assert_eq!(10 * 3 * 4, mesh.count_vertices());
+    }
+
+    #[test]
+    fn scroll_offset() {
+        let mut app = app();
+        let e = app
+            .world
+            .spawn(TerminalBundle::from(Terminal::new([10, 4])))
+            .id();
+        app.update();
+        app.update();
+
+        let bottom_left = |app: &App| {
+            let handle = app.world.get::<Mesh2dHandle>(e).unwrap();
+            let mesh = app.world.resource::<Assets<Mesh>>().get(&handle.0).unwrap();
+            let Some(VertexAttributeValues::Float32x3(verts)) =
+                mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            else {
+                panic!("Missing mesh positions");
+            };
+            // The second vertex of the first tile is it's bottom left corner
+            Vec2::from_slice(&verts[1])
+        };
+        let start = bottom_left(&app);
+
+        let offset = Vec2::new(0.5, -0.25);
+        app.world.entity_mut(e).insert(TerminalScrollOffset(offset));
+        app.update();
+
+        let tile_size = app.world.get::<TerminalLayout>(e).unwrap().tile_size;
+        assert_eq!(start + offset * tile_size, bottom_left(&app));
+
+        app.world.entity_mut(e).remove::<TerminalScrollOffset>();
+        app.update();
+        assert_eq!(start, bottom_left(&app));
+    }
+
+    #[test]
+    fn hidden_terminal_skips_rebuild() {
+        let mut app = app();
+        let e = app
+            .world
+            .spawn(TerminalBundle::from(Terminal::new([4, 1])))
+            .id();
+        app.update();
+        app.update();
+
+        let first_fg = |app: &App| {
+            let handle = app.world.get::<Mesh2dHandle>(e).unwrap();
+            let mesh = app.world.resource::<Assets<Mesh>>().get(&handle.0).unwrap();
+            let Some(VertexAttributeValues::Float32x4(fg)) =
+                mesh.attribute(mesh_data::ATTRIBUTE_COLOR_FG)
+            else {
+                panic!("Missing mesh colors");
+            };
+            fg[0]
+        };
+        let white = first_fg(&app);
+
+        *app.world.get_mut::<Visibility>(e).unwrap() = Visibility::Hidden;
+        app.update();
+        app.world
+            .get_mut::<Terminal>(e)
+            .unwrap()
+            .put_char([0, 0], 'a'.fg(Color::RED));
+        app.update();
+        assert_eq!(white, first_fg(&app));
+
+        *app.world.get_mut::<Visibility>(e).unwrap() = Visibility::Visible;
+        app.update();
+        assert_eq!(Color::RED.as_linear_rgba_f32(), first_fg(&app));
+    }
+
+    #[test]
+    fn aabb() {
+        let mut app = app();
+        let e = app
+            .world
+            .spawn(TerminalBundle::from(Terminal::new([10, 4])))
+            .id();
+
+        app.update();
+        app.update();
+
+        let aabb = app.world.get::<Aabb>(e).unwrap();
+        assert_eq!(Vec3A::new(5.0, 2.0, 0.0), aabb.half_extents);
+
+        app.world.get_mut::<Terminal>(e).unwrap().resize([20, 6]);
+        app.update();
+
+        let aabb = app.world.get::<Aabb>(e).unwrap();
+        assert_eq!(Vec3A::new(10.0, 3.0, 0.0), aabb.half_extents);
+    }
+}
@@ -3,10 +3,13 @@
 
 struct TerminalMaterial {
     clip_color: vec4<f32>,
+    outline_color: vec4<f32>,
     // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
     flags: u32,
 };
 const TERMINAL_MATERIAL_FLAGS_TEXTURE_BIT: u32 = 1u;
+const TERMINAL_MATERIAL_FLAGS_FALLBACK_TEXTURE_BIT: u32 = 2u;
+const TERMINAL_MATERIAL_FLAGS_GLYPH_OUTLINE_BIT: u32 = 4u;
 
 @group(0) @binding(0)
 var<uniform> view: View;
@@ -17,6 +20,10 @@ var<uniform> material: TerminalMaterial;
 var texture: texture_2d<f32>;
 @group(2) @binding(2)
 var texture_sampler: sampler;
+@group(2) @binding(3)
+var fallback_texture: texture_2d<f32>;
+@group(2) @binding(4)
+var fallback_sampler: sampler;
 
 @group(1) @binding(0)
 var<uniform> mesh: Mesh2d;
@@ -60,6 +67,31 @@ struct FragmentInput {
     @location(3) fg_color: vec4<f32>,
 };
 
+// Sample the glyph texture at the given uv, offset by a number of texels.
+fn sample_glyph(uv: vec2<f32>, texel_offset: vec2<f32>) -> vec4<f32> {
+    let texture_size = vec2<f32>(textureDimensions(texture));
+    var color = textureSample(texture, texture_sampler, uv + texel_offset / texture_size);
+
+    // Glyphs from the fallback texture have their uvs offset by 1 on
+    // the x axis. Both textures are sampled to keep control flow uniform.
+    if ((material.flags & TERMINAL_MATERIAL_FLAGS_FALLBACK_TEXTURE_BIT) != 0u) {
+        let fallback_size = vec2<f32>(textureDimensions(fallback_texture));
+        let fallback_uv = uv - vec2<f32>(1.0, 0.0) + texel_offset / fallback_size;
+        let fallback_color = textureSample(fallback_texture, fallback_sampler, fallback_uv);
+        color = select(color, fallback_color, uv.x >= 1.0);
+    }
+    return color;
+}
+
+// How much of a texel is covered by the glyph. Pixels matching the
+// clip color are pure background. For anti-aliased fonts the edges
+// fall somewhere between the clip color and the glyph color, or are
+// stored in the texture's alpha, and are used to blend fg over bg.
+fn glyph_coverage(tex_color: vec4<f32>, clip_rgb: vec3<f32>) -> f32 {
+    let diff = abs(tex_color.rgb - clip_rgb);
+    return clamp(tex_color.a * max(diff.r, max(diff.g, diff.b)), 0.0, 1.0);
+}
+
 @fragment
 fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
     
@@ -70,15 +102,44 @@ fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
     var out_color = fg_color;
 
     if ((material.flags & TERMINAL_MATERIAL_FLAGS_TEXTURE_BIT) != 0u) {
-        var tex_color = textureSample(texture, texture_sampler, in.uv);
+        var tex_color = sample_glyph(in.uv, vec2<f32>(0.0, 0.0));
 
         let tex_rgb = vec3<f32>(tex_color.rgb);
         let clip_rgb = vec3<f32>(clip_color.rgb);
-        
-        if( all(tex_rgb - clip_rgb < vec3<f32>(0.001, 0.001, 0.001)) ) {
+
+        let coverage = glyph_coverage(tex_color, clip_rgb);
+
+        // Draw the outline behind the glyph wherever a neighboring texel is
+        // covered. Note neighbors along the edge of a tile are sampled from
+        // the adjacent tile in the texture, fonts with glyphs touching the
+        // edges of their tiles may get stray outline pixels.
+        if ((material.flags & TERMINAL_MATERIAL_FLAGS_GLYPH_OUTLINE_BIT) != 0u) {
+            var neighbor = glyph_coverage(sample_glyph(in.uv, vec2<f32>(1.0, 0.0)), clip_rgb);
+            neighbor = max(neighbor, glyph_coverage(sample_glyph(in.uv, vec2<f32>(-1.0, 0.0)), clip_rgb));
+            neighbor = max(neighbor, glyph_coverage(sample_glyph(in.uv, vec2<f32>(0.0, 1.0)), clip_rgb));
+            neighbor = max(neighbor, glyph_coverage(sample_glyph(in.uv, vec2<f32>(0.0, -1.0)), clip_rgb));
+            let outline = neighbor * (1.0 - coverage) * material.outline_color.a;
+            bg_color = mix(bg_color, vec4<f32>(material.outline_color.rgb, 1.0), outline);
+        }
+
+        // Undo the darkening along anti-aliased edges so the glyph keeps
+        // its color, the edge is handled by the blend instead. Only grayscale
+        // texels are normalized, colored tilesets and fallback glyphs (IE:
+        // emoji) keep their own colors.
+        let tex_max = max(tex_rgb.r, max(tex_rgb.g, tex_rgb.b));
+        let tex_min = min(tex_rgb.r, min(tex_rgb.g, tex_rgb.b));
+        let is_fallback = (material.flags & TERMINAL_MATERIAL_FLAGS_FALLBACK_TEXTURE_BIT) != 0u && in.uv.x >= 1.0;
+        let is_gray = tex_max - tex_min < 0.01 && !is_fallback;
+        let glyph_rgb = select(tex_rgb, tex_rgb / max(tex_max, 0.0001), is_gray);
+
+        // Note tile colors are linear and the font texture is sampled as is,
+        // so coverage is applied in linear space. This can make light text on
+        // a dark background look slightly thinner than in gamma space.
+        let glyph_color = vec4<f32>(glyph_rgb * fg_color.rgb, fg_color.a);
+        if (coverage < 0.001) {
             out_color = bg_color;
         } else {
-            out_color = vec4<f32>(tex_color.rgb * fg_color.rgb, fg_color.a);
+            out_color = mix(bg_color, glyph_color, coverage);
         }
     }
     //return vec4<f32>(1.0, 0.0, 0.0, 1.0);
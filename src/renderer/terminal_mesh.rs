@@ -1,9 +1,17 @@
-use bevy::prelude::{Assets, Changed, GlobalTransform, Handle, Or, Query, Res};
+use bevy::{
+    prelude::{
+        Assets, Changed, DetectChanges, Entity, GlobalTransform, Handle, InheritedVisibility, Or,
+        Query, Ref, RemovedComponents, Res, Vec2, Without,
+    },
+    utils::HashSet,
+};
 use sark_grids::Size2d;
 
 use crate::{Terminal, TerminalLayout};
 
 use super::{
+    batch::TerminalBatch,
+    entity::TerminalScrollOffset,
     mesh_data::{TileData, UvMesher, VertData, VertMesher},
     uv_mapping::UvMapping,
 };
@@ -23,13 +31,28 @@ pub(crate) fn update_layout(
 #[allow(clippy::type_complexity)]
 pub(crate) fn update_vert_data(
     mut q_term: Query<
-        (&TerminalLayout, &mut VertData, &Handle<UvMapping>),
-        Changed<TerminalLayout>,
+        (
+            Entity,
+            Ref<TerminalLayout>,
+            &mut VertData,
+            &Handle<UvMapping>,
+            Option<Ref<TerminalScrollOffset>>,
+            Option<Ref<InheritedVisibility>>,
+        ),
+        Without<TerminalBatch>,
     >,
+    mut removed_scroll: RemovedComponents<TerminalScrollOffset>,
     mappings: Res<Assets<UvMapping>>,
 ) {
-    for (layout, mut verts, mapping) in &mut q_term {
-        if mappings.get(mapping).is_none() {
+    // Removing the scroll offset moves the mesh back to its origin, which
+    // a change filter can't detect
+    let removed_scroll: HashSet<Entity> = removed_scroll.read().collect();
+    for (entity, layout, mut verts, mapping, scroll, visibility) in &mut q_term {
+        let changed = layout.is_changed()
+            || scroll.as_ref().is_some_and(|s| s.is_changed())
+            || visibility.as_ref().is_some_and(|v| v.is_changed())
+            || removed_scroll.contains(&entity);
+        if !changed || mappings.get(mapping).is_none() || is_hidden(visibility.as_deref()) {
             continue;
         }
 
@@ -38,7 +61,9 @@ pub(crate) fn update_vert_data(
 
         //let origin = layout.origin();
         //println!("Origin {}", origin);
-        let mut mesher = VertMesher::new(layout.origin(), layout.tile_size, &mut verts);
+        let scroll = scroll.map_or(Vec2::ZERO, |s| s.0 * layout.tile_size);
+        let origin = layout.origin() + scroll;
+        let mut mesher = VertMesher::new(origin, layout.tile_size, &mut verts);
 
         // Note the order verts are added - uvs must be added in the same order!
         for i in 0..layout.term_size().len() {
@@ -52,23 +77,44 @@ pub(crate) fn update_vert_data(
 #[allow(clippy::type_complexity)]
 pub(crate) fn update_tile_data(
     mut q_term: Query<
-        (&Terminal, &mut TileData, &Handle<UvMapping>),
-        Or<(Changed<Terminal>, Changed<TerminalLayout>)>,
+        (
+            &Terminal,
+            &mut TileData,
+            &Handle<UvMapping>,
+            Option<&InheritedVisibility>,
+        ),
+        Or<(
+            Changed<Terminal>,
+            Changed<TerminalLayout>,
+            Changed<InheritedVisibility>,
+        )>,
     >,
     mappings: Res<Assets<UvMapping>>,
 ) {
-    for (term, mut tiles, mapping) in &mut q_term {
-        if mappings.get(mapping).is_none() {
+    for (term, mut tiles, mapping, visibility) in &mut q_term {
+        let Some(mapping) = mappings.get(mapping) else {
+            continue;
+        };
+        if is_hidden(visibility) {
             continue;
         }
         tiles.clear();
         tiles.reserve(term.size().len());
-        let mapping = mappings.get(mapping).unwrap();
         let mut mesher = UvMesher::new(mapping, &mut tiles);
 
         //println!("Updating tile data");
+        if let Some(tile) = term.filled_tile() {
+            mesher.fill_uvs(term.size().len(), tile.glyph, tile.fg_color, tile.bg_color);
+            continue;
+        }
         for tile in term.iter() {
             mesher.tile_uvs(tile.glyph, tile.fg_color, tile.bg_color);
         }
     }
 }
+
+/// Hidden terminals skip rebuilding their mesh data. The rebuild happens once
+/// they're visible again, since that changes their [`InheritedVisibility`].
+fn is_hidden(visibility: Option<&InheritedVisibility>) -> bool {
+    visibility.is_some_and(|v| !v.get())
+}
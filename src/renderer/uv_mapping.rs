@@ -4,23 +4,27 @@
 use bevy::{
     math::Vec2,
     prelude::{
-        Asset, AssetApp, AssetEvent, AssetId, Assets, DetectChangesMut, EventReader, Handle,
-        Plugin, Query, Update,
+        Asset, AssetApp, AssetEvent, AssetId, Assets, Color, DetectChanges, DetectChangesMut,
+        EventReader, Handle, Image, Plugin, Query, Ref, Res, ResMut, Update,
     },
     reflect::TypePath,
-    utils::HashMap,
+    utils::{HashMap, HashSet},
 };
 
-use crate::{code_page_437, TerminalLayout};
+use crate::{code_page_437, TerminalLayout, TerminalMaterial};
 
 use super::code_page_437::CP_437_CHARS;
 
 #[derive(Debug, Clone, Asset, TypePath)]
 pub struct UvMapping {
     uv_map: HashMap<char, [[f32; 2]; 4]>,
+    blank: HashSet<char>,
 }
 
 impl UvMapping {
+    /// The x offset applied to uvs which refer to the fallback texture.
+    pub const FALLBACK_UV_OFFSET: f32 = 1.0;
+
     pub fn code_page_437() -> Self {
         UvMapping::from_grid([16, 16], CP_437_CHARS.iter().cloned())
     }
@@ -37,7 +41,10 @@ impl UvMapping {
             uv_map.insert(ch, uvs);
         }
 
-        Self { uv_map }
+        Self {
+            uv_map,
+            blank: HashSet::default(),
+        }
     }
 
     pub fn get_grid_uvs(xy: [u32; 2], tile_count: [u32; 2]) -> [[f32; 2]; 4] {
@@ -54,6 +61,111 @@ impl UvMapping {
         ]
     }
 
+    /// Map glyphs which aren't already in the mapping to a second, fallback
+    /// texture (IE: for emoji), set via [`TerminalMaterial::fallback_texture`](crate::TerminalMaterial::fallback_texture).
+    ///
+    /// Each glyph is mapped to the given index on a tile sheet with
+    /// `tile_count` tiles. Fallback uvs are offset by
+    /// [`UvMapping::FALLBACK_UV_OFFSET`] on the x axis, which is how the
+    /// shader knows to sample the fallback texture.
+    pub fn with_fallback(mut self, glyphs: &HashMap<char, u8>, tile_count: [u32; 2]) -> Self {
+        for (ch, index) in glyphs.iter() {
+            let xy = [*index as u32 % tile_count[0], *index as u32 / tile_count[0]];
+            let mut uvs = Self::get_grid_uvs(xy, tile_count);
+            for uv in uvs.iter_mut() {
+                uv[0] += Self::FALLBACK_UV_OFFSET;
+            }
+            self.uv_map.entry(*ch).or_insert(uvs);
+        }
+        self
+    }
+
+    /// Returns true if the glyph is mapped to the fallback texture.
+    pub fn is_fallback(&self, ch: char) -> bool {
+        self.get_uvs(ch)
+            .is_some_and(|uvs| uvs[0][0] >= Self::FALLBACK_UV_OFFSET)
+    }
+
+    /// Inset every glyph's uv rect by the given number of texels on each side.
+    ///
+    /// This avoids neighbouring glyphs bleeding into each other when the
+    /// glyphs in a font atlas touch the edges of their cells. The mesh
+    /// geometry is unaffected. `texture_size` is the size of the font
+    /// texture in pixels.
+    pub fn with_uv_inset(mut self, texels: f32, texture_size: [u32; 2]) -> Self {
+        let inset = Vec2::splat(texels) / Vec2::new(texture_size[0] as f32, texture_size[1] as f32);
+        for uvs in self.uv_map.values_mut() {
+            let center = uvs.iter().map(|uv| Vec2::from(*uv)).sum::<Vec2>() / 4.0;
+            for uv in uvs.iter_mut() {
+                let p = Vec2::from(*uv);
+                // Move each corner towards the center of the rect
+                let dir = (center - p).signum();
+                *uv = (p + dir * inset).into();
+            }
+        }
+        self
+    }
+
+    /// Analyze a font image to find which glyphs have no visible pixels, see
+    /// [`UvMapping::glyph_is_blank`].
+    ///
+    /// A pixel is visible if it isn't fully transparent and doesn't match
+    /// `clip_color`, the same as the terminal shader. This should be the
+    /// font's [`TerminalMaterial::bg_clip_color`](crate::TerminalMaterial::bg_clip_color).
+    /// Only 8 bit RGBA images (such as the built in fonts) can be analyzed,
+    /// for any other format no glyphs are considered blank.
+    ///
+    /// This is done automatically for a terminal's mapping when it's font
+    /// image finishes loading.
+    pub fn with_blank_glyphs(mut self, image: &Image, clip_color: Color) -> Self {
+        self.find_blank_glyphs(image, clip_color);
+        self
+    }
+
+    fn find_blank_glyphs(&mut self, image: &Image, clip_color: Color) {
+        self.blank.clear();
+        let size = image.size();
+        if image.data.len() != (size.x * size.y * 4) as usize {
+            return;
+        }
+        let clip = clip_color.as_rgba_u8();
+        let visible = |x: u32, y: u32| {
+            let i = ((y * size.x + x) * 4) as usize;
+            let px = &image.data[i..i + 4];
+            px[3] != 0 && px[..3] != clip[..3]
+        };
+        for (ch, uvs) in self.uv_map.iter() {
+            let min = uvs.iter().fold(Vec2::MAX, |a, b| a.min(Vec2::from(*b)));
+            let max = uvs.iter().fold(Vec2::MIN, |a, b| a.max(Vec2::from(*b)));
+            // Fallback glyphs are on a different texture
+            if min.x >= Self::FALLBACK_UV_OFFSET {
+                continue;
+            }
+            let min = (min * size.as_vec2()).round().as_uvec2();
+            let max = (max * size.as_vec2()).round().as_uvec2().min(size);
+            let has_ink = (min.y..max.y).any(|y| (min.x..max.x).any(|x| visible(x, y)));
+            if !has_ink {
+                self.blank.insert(*ch);
+            }
+        }
+    }
+
+    /// Returns true if the glyph's tile in the font image has no visible
+    /// pixels, IE: a space. This is only known after the mapping has
+    /// analyzed a font via [`UvMapping::with_blank_glyphs`], otherwise it
+    /// always returns false.
+    ///
+    /// Note a mapping may be shared by terminals with different fonts, in
+    /// which case this reflects the most recently loaded font.
+    pub fn glyph_is_blank(&self, ch: char) -> bool {
+        self.blank.contains(&ch)
+    }
+
+    /// Retrieve the uvs for a glyph, if it's present in the mapping.
+    pub fn get_uvs(&self, ch: char) -> Option<&[[f32; 2]; 4]> {
+        self.uv_map.get(&ch)
+    }
+
     pub fn uvs_from_glyph(&self, ch: char) -> &[[f32; 2]; 4] {
         self.uv_map.get(&ch).unwrap_or_else(|| {
             panic!(
@@ -80,7 +192,7 @@ pub struct UvMappingPlugin;
 impl Plugin for UvMappingPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.init_asset::<UvMapping>()
-            .add_systems(Update, uv_mapping_loaded);
+            .add_systems(Update, (uv_mapping_loaded, find_blank_glyphs));
         app.world
             .resource_mut::<Assets<UvMapping>>()
             .insert(Handle::<UvMapping>::default(), UvMapping::code_page_437());
@@ -109,3 +221,148 @@ pub(crate) fn uv_mapping_loaded(
         }
     }
 }
+
+/// Analyze a terminal's font for blank glyphs whenever it's font image or
+/// material finishes loading or changes, see [`UvMapping::glyph_is_blank`].
+pub(crate) fn find_blank_glyphs(
+    mut ev_image: EventReader<AssetEvent<Image>>,
+    mut ev_material: EventReader<AssetEvent<TerminalMaterial>>,
+    q_term: Query<(Ref<Handle<TerminalMaterial>>, &Handle<UvMapping>)>,
+    materials: Res<Assets<TerminalMaterial>>,
+    images: Res<Assets<Image>>,
+    mut mappings: ResMut<Assets<UvMapping>>,
+) {
+    let images_loaded: HashSet<_> = ev_image.read().filter_map(loaded_id).collect();
+    let materials_loaded: HashSet<_> = ev_material.read().filter_map(loaded_id).collect();
+
+    for (material_handle, mapping) in &q_term {
+        let Some(material) = materials.get(&*material_handle) else {
+            continue;
+        };
+        let Some(image_handle) = material.texture.as_ref() else {
+            continue;
+        };
+        let changed = material_handle.is_changed()
+            || materials_loaded.contains(&material_handle.id())
+            || images_loaded.contains(&image_handle.id());
+        if !changed {
+            continue;
+        }
+        let (Some(image), Some(mapping)) = (images.get(image_handle), mappings.get_mut(mapping))
+        else {
+            continue;
+        };
+        mapping.find_blank_glyphs(image, material.bg_clip_color);
+    }
+}
+
+/// The asset from an event if it was added or its contents changed.
+fn loaded_id<A: Asset>(ev: &AssetEvent<A>) -> Option<AssetId<A>> {
+    match ev {
+        AssetEvent::Added { id }
+        | AssetEvent::LoadedWithDependencies { id }
+        | AssetEvent::Modified { id } => Some(*id),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        prelude::{App, AssetPlugin},
+        render::{
+            render_asset::RenderAssetUsages,
+            texture::{CompressedImageFormats, ImageSampler, ImageType},
+        },
+        MinimalPlugins,
+    };
+
+    use super::*;
+
+    #[test]
+    fn fallback() {
+        let glyphs = HashMap::from_iter([('😀', 0), ('🔥', 5), ('A', 1)]);
+        let mapping = UvMapping::code_page_437().with_fallback(&glyphs, [4, 4]);
+
+        assert!(mapping.is_fallback('😀'));
+        assert!(mapping.is_fallback('🔥'));
+        // Glyphs in the main texture take precedence
+        assert!(!mapping.is_fallback('A'));
+
+        // Index 5 is [1, 1] on a 4x4 sheet
+        let uvs = mapping.uvs_from_glyph('🔥');
+        assert_eq!([1.0 + 0.25, 0.25], uvs[0]);
+        assert_eq!([1.0 + 0.5, 0.5], uvs[3]);
+    }
+
+    /// The built in 8x8 font
+    fn font_image() -> Image {
+        Image::from_buffer(
+            include_bytes!("builtin/px437_8x8.png"),
+            ImageType::Extension("png"),
+            CompressedImageFormats::NONE,
+            false,
+            ImageSampler::default(),
+            RenderAssetUsages::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn blank_glyphs() {
+        let image = font_image();
+        let mapping = UvMapping::code_page_437();
+        assert!(!mapping.glyph_is_blank(' '));
+
+        let mapping = mapping.with_blank_glyphs(&image, Color::BLACK);
+        assert!(mapping.glyph_is_blank(' '));
+        assert!(!mapping.glyph_is_blank('#'));
+        assert!(!mapping.glyph_is_blank('█'));
+        assert!(!mapping.glyph_is_blank('☺'));
+    }
+
+    #[test]
+    fn blank_glyphs_on_load() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_asset::<TerminalMaterial>()
+            .init_asset::<UvMapping>()
+            .add_systems(Update, find_blank_glyphs);
+
+        let mapping = app
+            .world
+            .resource_mut::<Assets<UvMapping>>()
+            .add(UvMapping::code_page_437());
+        let image = app.world.resource_mut::<Assets<Image>>().add(font_image());
+        let material = app
+            .world
+            .resource_mut::<Assets<TerminalMaterial>>()
+            .add(TerminalMaterial::from(image));
+        app.world.spawn((material, mapping.clone()));
+        app.update();
+
+        let mappings = app.world.resource::<Assets<UvMapping>>();
+        let mapping = mappings.get(&mapping).unwrap();
+        assert!(mapping.glyph_is_blank(' '));
+        assert!(!mapping.glyph_is_blank('#'));
+    }
+
+    #[test]
+    fn uv_inset() {
+        // 16x16 tiles of 8x8 pixels
+        let mapping = UvMapping::code_page_437().with_uv_inset(1.0, [128, 128]);
+
+        // 'A' is index 65, cell [1, 4]
+        let uvs = mapping.uvs_from_glyph('A');
+        let texel = 1.0 / 128.0;
+        let min = Vec2::new(1.0 / 16.0, 4.0 / 16.0) + texel;
+        let max = Vec2::new(2.0 / 16.0, 5.0 / 16.0) - texel;
+
+        let uvs: Vec<Vec2> = uvs.iter().map(|uv| Vec2::from(*uv)).collect();
+        let actual_min = uvs.iter().fold(Vec2::ONE, |a, b| a.min(*b));
+        let actual_max = uvs.iter().fold(Vec2::ZERO, |a, b| a.max(*b));
+        assert!(min.abs_diff_eq(actual_min, 1e-6));
+        assert!(max.abs_diff_eq(actual_max, 1e-6));
+    }
+}
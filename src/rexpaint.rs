@@ -0,0 +1,244 @@
+//! Loading art made in [REXPaint](https://www.gridsagegames.com/rexpaint/)
+//! from `.xp` files.
+use std::io::{self, Read};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::{App, Asset, AssetApp, Color, Plugin},
+    reflect::TypePath,
+    utils::BoxedFuture,
+};
+use flate2::read::GzDecoder;
+
+use crate::{code_page_437, Terminal, Tile};
+
+/// Adds an asset loader for REXPaint `.xp` files.
+///
+/// Assets are hot reloaded like any other asset if bevy's `file_watcher`
+/// feature is enabled, so art can be edited in REXPaint while the game is
+/// running.
+///
+/// # Example
+///
+/// ```rust no_run
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::prelude::*;
+///
+/// #[derive(Resource)]
+/// struct Map(Handle<XpArt>);
+///
+/// fn load(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.insert_resource(Map(server.load("map.xp")));
+/// }
+///
+/// fn spawn(mut commands: Commands, map: Res<Map>, art: Res<Assets<XpArt>>) {
+///     if let Some(art) = art.get(&map.0) {
+///         commands.spawn(TerminalBundle::from(art.to_terminal()));
+///     }
+/// }
+/// ```
+pub struct XpArtPlugin;
+
+impl Plugin for XpArtPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<XpArt>().register_asset_loader(XpArtLoader);
+    }
+}
+
+/// The background color REXPaint uses to mark a transparent cell.
+const TRANSPARENT_BG: [u8; 3] = [255, 0, 255];
+
+/// Art loaded from a REXPaint `.xp` file, see [`Terminal::from_rexpaint_bytes`].
+#[derive(Asset, TypePath, Debug, Clone, Default)]
+pub struct XpArt {
+    terminal: Terminal,
+}
+
+impl XpArt {
+    /// Read art from the contents of an `.xp` file.
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        Terminal::from_rexpaint_bytes(bytes).map(|terminal| Self { terminal })
+    }
+
+    /// The flattened art.
+    pub fn terminal(&self) -> &Terminal {
+        &self.terminal
+    }
+
+    /// Create a terminal with the art written to it.
+    pub fn to_terminal(&self) -> Terminal {
+        self.terminal.clone()
+    }
+}
+
+impl Terminal {
+    /// Create a terminal from the contents of a REXPaint `.xp` file, IE: from
+    /// `include_bytes!`. Doesn't need filesystem access, so it also works
+    /// on platforms like wasm.
+    ///
+    /// All of the file's layers are flattened into a single terminal, with
+    /// transparent cells on upper layers showing the layers beneath them.
+    pub fn from_rexpaint_bytes(bytes: &[u8]) -> io::Result<Terminal> {
+        let mut data = Vec::new();
+        GzDecoder::new(bytes).read_to_end(&mut data)?;
+        let mut reader = data.as_slice();
+
+        let _version = read_i32(&mut reader)?;
+        let layer_count = read_i32(&mut reader)?;
+        let mut terminal: Option<Terminal> = None;
+        for _ in 0..layer_count {
+            let width = read_i32(&mut reader)?;
+            let height = read_i32(&mut reader)?;
+            if width <= 0 || height <= 0 {
+                return Err(invalid_data("Layer size must be non-zero"));
+            }
+            // Each cell is a 4 byte glyph and two 3 byte colors. Check the
+            // data is really there before trusting the size from the header
+            let layer_len = (width as usize)
+                .checked_mul(height as usize)
+                .and_then(|cells| cells.checked_mul(10));
+            match layer_len {
+                Some(len) if len <= reader.len() => {}
+                _ => return Err(invalid_data("Layer size doesn't match the file's data")),
+            }
+            let term = terminal.get_or_insert_with(|| Terminal::new([width as u32, height as u32]));
+            if term.size().as_ivec2().to_array() != [width, height] {
+                return Err(invalid_data("Every layer must be the same size"));
+            }
+
+            // Cells are stored in columns, from the top of the image down
+            for x in 0..width {
+                for y in (0..height).rev() {
+                    let code = read_i32(&mut reader)?;
+                    let code = u8::try_from(code)
+                        .map_err(|_| invalid_data("Glyph is outside code page 437"))?;
+                    let mut colors = [0; 6];
+                    Read::read_exact(&mut reader, &mut colors)?;
+                    let [fr, fg, fb, br, bg, bb] = colors;
+                    if [br, bg, bb] == TRANSPARENT_BG {
+                        continue;
+                    }
+                    let tile = Tile {
+                        glyph: code_page_437::index_to_glyph(code),
+                        fg_color: Color::rgb_u8(fr, fg, fb),
+                        bg_color: Color::rgb_u8(br, bg, bb),
+                    };
+                    term.put_tile([x, y], tile);
+                }
+            }
+        }
+
+        terminal.ok_or_else(|| invalid_data("File has no layers"))
+    }
+}
+
+fn read_i32(reader: &mut impl Read) -> io::Result<i32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(i32::from_le_bytes(bytes))
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[derive(Default)]
+struct XpArtLoader;
+
+impl AssetLoader for XpArtLoader {
+    type Asset = XpArt;
+    type Settings = ();
+    type Error = io::Error;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<XpArt, io::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            XpArt::from_bytes(&bytes)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["xp"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use bevy::{
+        asset::{AssetPlugin, AssetServer, Assets},
+        MinimalPlugins,
+    };
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    #[test]
+    fn from_rexpaint_bytes() {
+        // A 2x2 image with two layers, the top layer is transparent except
+        // for a red 'x' in the bottom left
+        let bytes = include_bytes!("../assets/rexpaint_test.xp");
+
+        let term = Terminal::from_rexpaint_bytes(bytes).unwrap();
+        assert_eq!([2, 2], term.size().to_array());
+        term.assert_looks_like(
+            "
+ac
+x",
+        );
+        assert_eq!(Color::rgb_u8(255, 0, 0), term.get_tile([0, 0]).fg_color);
+
+        assert!(Terminal::from_rexpaint_bytes(&bytes[..10]).is_err());
+    }
+
+    #[test]
+    fn size_larger_than_data() {
+        // A single layer claiming to be 50000x50000 with only one cell
+        let mut data = Vec::new();
+        for i in [-1, 1, 50000, 50000, 0] {
+            data.extend(i32::to_le_bytes(i));
+        }
+        data.extend([0; 6]);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&data).unwrap();
+        let bytes = encoder.finish().unwrap();
+
+        let err = Terminal::from_rexpaint_bytes(&bytes).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    }
+
+    #[test]
+    fn load_asset() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin {
+                file_path: concat!(env!("CARGO_MANIFEST_DIR"), "/assets").to_string(),
+                ..Default::default()
+            },
+            XpArtPlugin,
+        ));
+        let handle = app
+            .world
+            .resource::<AssetServer>()
+            .load::<XpArt>("rexpaint_test.xp");
+
+        for _ in 0..100 {
+            app.update();
+            if app.world.resource::<Assets<XpArt>>().contains(&handle) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        let arts = app.world.resource::<Assets<XpArt>>();
+        let art = arts.get(&handle).expect("Art should have loaded");
+        assert_eq!('x', art.terminal().get_char([0, 0]));
+    }
+}
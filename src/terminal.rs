@@ -1,4 +1,8 @@
+use std::cmp::Ordering;
 use std::ops::Div;
+use std::ops::Index;
+use std::ops::IndexMut;
+use std::ops::Range;
 use std::ops::RangeBounds;
 use std::ops::Sub;
 
@@ -7,17 +11,22 @@ use bevy::math::UVec2;
 use bevy::prelude::Color;
 use bevy::prelude::Component;
 use bevy::prelude::Vec2;
+use bevy::prelude::Vec4;
 
 use sark_grids::geometry::GridRect;
 use sark_grids::grid::Side;
 use sark_grids::Grid;
 use sark_grids::GridPoint;
+use sark_grids::Pivot;
 use sark_grids::Size2d;
 
 use crate::border::Border;
+use crate::code_page_437;
 use crate::fmt_tile::ColorFormat;
+use crate::formatting::FormattedString;
 use crate::formatting::StringFormatter;
 use crate::TileFormatter;
+use crate::ToWorld;
 
 /// A simple terminal for writing text in a readable grid.
 ///
@@ -50,11 +59,37 @@ pub struct Terminal {
     /// terminal positions and sizes do not include the border unless otherwise
     /// specified.
     border: Option<Border>,
+    /// If set, positions outside the terminal wrap around to the opposite
+    /// side instead of being out of bounds, IE: for a grid with Pac-Man style
+    /// tunnels. Writing to `[-1, 0]` will write to the rightmost tile of the
+    /// bottom row.
+    ///
+    /// This only affects how positions are converted to tile indices,
+    /// [`Terminal::in_bounds`] still reports the actual terminal bounds.
+    /// Defaults to `false`.
+    pub wrap_addressing: bool,
+    /// Scratch buffer reused when writing formatted text, to avoid allocating
+    /// a new string every time. Any write which needs to build an
+    /// intermediate string should use this rather than allocating.
+    string_buffer: String,
+    /// Set whenever the terminal's tiles or border are modified.
+    dirty: bool,
+    /// Set when every tile is known to be the same tile, IE: directly after
+    /// a [`Terminal::clear`] or [`Terminal::fill`]. Any other tile
+    /// modification unsets it. Lets the renderer build the mesh in bulk
+    /// rather than per tile.
+    filled: Option<Tile>,
 }
 
 /// A single tile of the terminal.
 ///
 /// Defaults to a blank glyph with a black background and a white foreground.
+///
+/// Tile colors are regular bevy [`Color`]s. Colors created with `Color::rgb`
+/// are in sRGB space, the same as colors picked in an image editor, and are
+/// converted to linear space by the renderer. Use `Color::rgb_linear` if you
+/// already have linear values - passing linear values to `Color::rgb` will
+/// make colors look washed out.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Tile {
     /// The glyph for the tile. Glyphs are mapped to sprites via the
@@ -70,6 +105,21 @@ impl Tile {
     pub const DEFAULT_FGCOL: Color = Color::WHITE;
     pub const DEFAULT_BGCOL: Color = Color::BLACK;
 
+    /// Set the foreground color from sRGB components in the range `0.0..=1.0`.
+    pub fn fg_srgb(&mut self, rgb: [f32; 3]) {
+        self.fg_color = Color::rgb(rgb[0], rgb[1], rgb[2]);
+    }
+
+    /// Set the background color from sRGB components in the range `0.0..=1.0`.
+    pub fn bg_srgb(&mut self, rgb: [f32; 3]) {
+        self.bg_color = Color::rgb(rgb[0], rgb[1], rgb[2]);
+    }
+
+    /// Returns true if both tiles have the same glyph, ignoring their colors.
+    pub fn same_glyph(&self, other: &Tile) -> bool {
+        self.glyph == other.glyph
+    }
+
     /// Create an invisible tile.
     pub fn transparent() -> Tile {
         Tile {
@@ -99,39 +149,188 @@ impl From<char> for Tile {
     }
 }
 
+/// Options for [`Terminal::flood_fill`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FloodFillOptions {
+    /// If true, tiles must match the starting tile's glyph and colors to be
+    /// filled. If false, only the glyph is compared.
+    pub match_colors: bool,
+}
+
+impl Default for FloodFillOptions {
+    fn default() -> Self {
+        Self { match_colors: true }
+    }
+}
+
+impl FloodFillOptions {
+    /// Options for a fill that only compares glyphs, ignoring colors.
+    pub fn glyph_only() -> Self {
+        Self {
+            match_colors: false,
+        }
+    }
+}
+
+/// Error returned when attempting to create a terminal with an invalid size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalSizeError {
+    /// The size that was rejected.
+    pub size: UVec2,
+}
+
+impl std::fmt::Display for TerminalSizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid terminal size {}, terminal width and height must be non-zero",
+            self.size
+        )
+    }
+}
+
+impl std::error::Error for TerminalSizeError {}
+
+/// Error returned when setting a terminal's tiles from a slice of the wrong
+/// length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileCountError {
+    /// The number of tiles in the terminal.
+    pub expected: usize,
+    /// The number of tiles that were given.
+    pub actual: usize,
+}
+
+impl std::fmt::Display for TileCountError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected {} tiles to match the terminal size, got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for TileCountError {}
+
 impl Terminal {
-    /// Construct a terminal with the given size
+    /// The minimum width and height of a terminal. Terminals are never
+    /// resized below this.
+    pub const MIN_SIZE: UVec2 = UVec2::ONE;
+
+    /// Construct a terminal with the given size. Each dimension is clamped to
+    /// a minimum of [`Terminal::MIN_SIZE`], use [`Terminal::try_new`] to
+    /// reject invalid sizes instead.
     pub fn new(size: impl Size2d) -> Terminal {
+        let size = size.as_uvec2().max(Self::MIN_SIZE);
         let clear_tile = Tile::default();
         Terminal {
             tiles: Grid::new(size),
-            size: size.as_uvec2(),
+            size,
             clear_tile,
+            dirty: true,
+            filled: Some(clear_tile),
             ..Default::default()
         }
     }
 
+    /// Construct a terminal with the given size, returning an error if either
+    /// dimension is zero.
+    pub fn try_new(size: impl Size2d) -> Result<Terminal, TerminalSizeError> {
+        let size = size.as_uvec2();
+        if size.cmplt(Self::MIN_SIZE).any() {
+            return Err(TerminalSizeError { size });
+        }
+        Ok(Terminal::new(size))
+    }
+
+    /// Construct a terminal from a flat list of cells, as returned by
+    /// [`Terminal::to_cells`].
+    ///
+    /// Each cell is a glyph followed by its foreground and background colors
+    /// as sRGBA components. Cells are in row-major order, starting from the
+    /// bottom left of the terminal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of cells doesn't match the given size.
+    pub fn from_cells(size: impl Size2d, cells: &[(char, [f32; 4], [f32; 4])]) -> Terminal {
+        assert_eq!(
+            size.len(),
+            cells.len(),
+            "Cell count doesn't match terminal size {:?}",
+            size.as_array()
+        );
+        let mut term = Terminal::new(size);
+        for (t, (glyph, fg, bg)) in term.iter_mut().zip(cells) {
+            t.glyph = *glyph;
+            t.fg_color = Color::rgba(fg[0], fg[1], fg[2], fg[3]);
+            t.bg_color = Color::rgba(bg[0], bg[1], bg[2], bg[3]);
+        }
+        term
+    }
+
     /// Specify a border for the terminal.
     ///
     /// The terminal border is considered separate from the terminal itself,
     /// writes and sizes within the terminal will ignore the border unless
     /// otherwise specified.
+    ///
+    /// The result can be written to as normal and then spawned via a
+    /// [`TerminalBundle`](crate::TerminalBundle), which will pick up the border.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// fn setup(mut commands: Commands) {
+    ///     let mut term = Terminal::new([10, 3]).with_border(Border::single_line());
+    ///     term.put_string([0, 0], "Hello");
+    ///     commands.spawn(TerminalBundle::from(term));
+    /// }
+    /// ```
     pub fn with_border(mut self, border: Border) -> Self {
         self.border = Some(border);
         self
     }
 
+    /// Specify the terminal's clear tile and clear the terminal to it.
+    ///
+    /// The clear tile's colors are rendered as is, including their alpha. A
+    /// clear tile with a fully transparent background (such as
+    /// [`Tile::transparent`]) will let anything behind the terminal show
+    /// through any empty tiles.
     pub fn with_clear_tile(mut self, clear_tile: impl Into<Tile>) -> Self {
         self.clear_tile = clear_tile.into();
         self.clear();
         self
     }
 
+    /// Change the terminal's clear tile at runtime.
+    ///
+    /// Only tiles exactly equal to the previous clear tile are replaced with
+    /// the new one, anything that was written to the terminal is left as is.
+    pub fn set_clear_tile(&mut self, clear_tile: impl Into<Tile>) {
+        let old = self.clear_tile;
+        let new = clear_tile.into();
+        let filled = self.filled;
+        self.clear_tile = new;
+        for t in self.tiles_mut().iter_mut().filter(|t| **t == old) {
+            *t = new;
+        }
+        // A filled terminal is still entirely one tile
+        self.filled = filled.map(|t| if t == old { new } else { t });
+    }
+
     pub fn set_border(&mut self, border: Border) {
+        self.dirty = true;
         self.border = Some(border);
     }
 
     pub fn remove_border(&mut self) {
+        self.dirty = true;
         self.border = None;
     }
 
@@ -140,15 +339,94 @@ impl Terminal {
     }
 
     pub fn border_mut(&mut self) -> Option<&mut Border> {
+        self.dirty = true;
         self.border.as_mut()
     }
 
+    /// Returns true if the terminal has been modified since the last call to
+    /// [`Terminal::take_dirty`]. A newly created terminal is dirty.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns true if the terminal has been modified since the last call to
+    /// this function, and resets the flag.
+    ///
+    /// The flag is set by any function that mutably accesses the terminal's
+    /// tiles or border, whether or not anything actually changed. This is
+    /// independent from bevy's change detection - note that calling this
+    /// through a `Mut<Terminal>` will itself trigger change detection, causing
+    /// the terminal mesh to be rebuilt. Use `bypass_change_detection` to
+    /// avoid that:
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// fn redraw(mut q_term: Query<&mut Terminal>) {
+    ///     for mut term in &mut q_term {
+    ///         if term.bypass_change_detection().take_dirty() {
+    ///             // The terminal was changed since it was last checked
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    /// Returns the tile every tile in the terminal is set to, IE: the
+    /// `clear_tile` if it was just cleared.
+    ///
+    /// This is conservative - it's only set by clearing or filling the whole
+    /// terminal and is unset by any other tile modification, even if the
+    /// terminal ends up entirely one tile again.
+    pub(crate) fn filled_tile(&self) -> Option<Tile> {
+        self.filled
+    }
+
+    /// Mutable access to the terminal's tiles, marking the terminal as dirty.
+    fn tiles_mut(&mut self) -> &mut Grid<Tile> {
+        self.dirty = true;
+        self.filled = None;
+        &mut self.tiles
+    }
+
     /// Resize the terminal.
     ///
-    /// This will clear the terminal.
+    /// This will clear the terminal to the terminal's `clear_tile`. Each
+    /// dimension is clamped to a minimum of [`Terminal::MIN_SIZE`].
     pub fn resize(&mut self, size: impl Size2d) {
-        self.tiles = Grid::new(size);
-        self.size = size.as_uvec2();
+        let size = size.as_uvec2().max(Self::MIN_SIZE);
+        *self.tiles_mut() = Grid::new(size);
+        self.size = size;
+        self.clear();
+    }
+
+    /// Resize the terminal, keeping any content that still fits.
+    ///
+    /// Content is anchored to the given pivot of the terminal, IE: with
+    /// [`Pivot::TopLeft`] the top left tile stays at the top left, with
+    /// [`Pivot::Center`] the center tile stays at the center. New tiles are
+    /// set to the terminal's `clear_tile`. As with [`Terminal::resize`], each
+    /// dimension is clamped to a minimum of [`Terminal::MIN_SIZE`].
+    pub fn resize_anchored(&mut self, size: impl Size2d, pivot: Pivot) {
+        let size = size.as_uvec2().max(Self::MIN_SIZE);
+        let old_size = self.size;
+        let offset = (size.as_ivec2() - old_size.as_ivec2()).as_vec2() * Vec2::from(pivot);
+        let offset = offset.round().as_ivec2();
+
+        let old = std::mem::replace(self.tiles_mut(), Grid::new(size));
+        self.size = size;
+        self.clear();
+
+        let width = old_size.x as usize;
+        for (i, tile) in old.iter().enumerate() {
+            let xy = IVec2::new((i % width) as i32, (i / width) as i32) + offset;
+            if self.in_bounds(xy) {
+                self.put_tile(xy, *tile);
+            }
+        }
     }
 
     /// The width of the terminal, excluding the border.
@@ -201,6 +479,10 @@ impl Terminal {
     /// 1d index
     #[inline]
     pub fn transform_lti(&self, xy: impl GridPoint) -> usize {
+        if self.wrap_addressing {
+            let xy = xy.as_ivec2().rem_euclid(self.size.as_ivec2());
+            return self.tiles.transform_lti(xy);
+        }
         self.tiles.transform_lti(xy)
     }
 
@@ -261,6 +543,70 @@ impl Terminal {
         *t = tile;
     }
 
+    /// Blend a tile over the existing tile at the given position, IE: for
+    /// fading a translucent overlay in or out.
+    ///
+    /// `alpha` is clamped to `0.0..=1.0`. The existing tile's foreground and
+    /// background colors are linearly interpolated towards the new tile's
+    /// colors by `alpha`, per sRGBA component. The glyph is replaced once
+    /// the new tile is at least half opaque, IE: when `alpha >= 0.5`. Does
+    /// nothing if the position is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// let tile = Tile { glyph: '!', fg_color: Color::RED, bg_color: Color::BLACK };
+    /// term.blend_tile([1,1], tile, 0.25);
+    /// assert_eq!(' ', term.get_char([1,1]));
+    /// ```
+    pub fn blend_tile(&mut self, xy: impl GridPoint, tile: Tile, alpha: f32) {
+        if !self.in_bounds(xy) {
+            return;
+        }
+        let alpha = alpha.clamp(0.0, 1.0);
+        let blend = |a: Color, b: Color| {
+            let a = Vec4::from(a.as_rgba_f32());
+            let b = Vec4::from(b.as_rgba_f32());
+            let [r, g, b, a] = a.lerp(b, alpha).to_array();
+            Color::rgba(r, g, b, a)
+        };
+        let t = self.get_tile_mut(xy);
+        t.fg_color = blend(t.fg_color, tile.fg_color);
+        t.bg_color = blend(t.bg_color, tile.bg_color);
+        if alpha >= 0.5 {
+            t.glyph = tile.glyph;
+        }
+    }
+
+    /// Swap the tiles at the two given positions.
+    ///
+    /// Does nothing if either position is out of bounds.
+    pub fn swap_tiles(&mut self, a: impl GridPoint, b: impl GridPoint) {
+        if !self.in_bounds(a) || !self.in_bounds(b) {
+            return;
+        }
+        let a = self.transform_lti(a);
+        let b = self.transform_lti(b);
+        self.tiles_mut().slice_mut().swap(a, b);
+    }
+
+    /// Move a tile to a new position, replacing the tile at the original
+    /// position with the terminal's `clear_tile`.
+    ///
+    /// Does nothing if either position is out of bounds.
+    pub fn move_tile(&mut self, from: impl GridPoint, to: impl GridPoint) {
+        if !self.in_bounds(from) || !self.in_bounds(to) {
+            return;
+        }
+        let tile = *self.get_tile(from);
+        self.put_tile(from, self.clear_tile);
+        self.put_tile(to, tile);
+    }
+
     /// Write a formatted string to the terminal.
     ///
     /// The [`StringFormatter`] trait allows you to optionally specify a foreground
@@ -296,51 +642,438 @@ impl Terminal {
     /// // Write a mutli-line string to the center of the terminal
     /// term.put_string([0,0].pivot(Pivot::Center), "Hello\nHow are you?");
     /// ```
+    ///
+    /// Each line of the string is written exactly as given - leading
+    /// whitespace after a line return is not trimmed, so indented text will
+    /// keep it's indentation.
+    ///
+    /// Wide glyphs such as CJK characters take up two columns. The glyph is
+    /// written to the first tile and the second is set to a space, so text
+    /// after it lines up as it would in a text terminal. The glyph itself is
+    /// still rendered in a single tile.
     pub fn put_string<'a>(&mut self, xy: impl GridPoint, writer: impl StringFormatter<'a> + 'a) {
+        self.append_string(xy, writer);
+    }
+
+    /// Write a formatted string to the terminal, as with [`Terminal::put_string`],
+    /// and return the position one tile to the right of the last glyph of the
+    /// string so further text can be appended to it.
+    ///
+    /// For multi-line strings this is the end of the last line. For an empty
+    /// string it's the position the string would have been written to. The
+    /// returned position isn't bounds checked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    ///
+    /// let mut term = Terminal::new([10,1]);
+    /// let end = term.append_string([0,0], "HP: ");
+    /// term.put_string(end, "100".fg(Color::RED));
+    /// assert_eq!("HP: 100", term.get_string([0,0], 7));
+    /// ```
+    pub fn append_string<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+    ) -> IVec2 {
+        let fmt = writer.formatted();
+        // Fill first so a background color set on the string takes precedence
+        if fmt.fill_width.is_some() {
+            let mut line_starts: Vec<_> = self
+                .string_tiles(xy, fmt.visible())
+                .map(|(p, _)| p)
+                .collect();
+            line_starts.dedup_by_key(|p| p.y);
+            for start in line_starts {
+                self.fill_line(start, &fmt, i32::MAX);
+            }
+        }
+        self.write_string(xy, IVec2::ZERO, fmt.visible(), |t, ch| fmt.write(t, ch))
+            .unwrap_or_else(|| self.tiles.pivoted_point(xy))
+    }
+
+    /// Write a formatted string to the terminal, wrapping it within the given
+    /// area, IE: for side-by-side panels within a single terminal.
+    ///
+    /// The string is written from `xy`, and each new line starts from the
+    /// left edge of `rect`. A pivoted `xy` is relative to that pivot of
+    /// `rect` rather than of the terminal, IE: `[1,1].pivot(Pivot::TopLeft)`
+    /// starts one tile in from the top left corner of the area. Lines which
+    /// are too long for the area are wrapped at the last space that fits,
+    /// words longer than the area's width are split. Anything outside of
+    /// `rect` or below its bottom edge isn't written.
+    ///
+    /// Returns the position the next glyph would be written to, as with
+    /// [`Terminal::append_string`]. If the string didn't fit this will be
+    /// below the bottom of `rect`, or below the last line written if the
+    /// string was cut off by [`StringFormatter::max_lines`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([20,10]);
+    /// let panel = GridRect::from_bl([10,0], [10u32,10]);
+    /// term.put_string_in(panel, [10,9], "This text stays in the right panel");
+    /// ```
+    pub fn put_string_in<'a>(
+        &mut self,
+        rect: GridRect,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+    ) -> IVec2 {
+        let fmt = writer.formatted();
+        let min = rect.min_i();
+        let max = rect.max_i();
+        let width = (max.x - min.x + 1).max(0);
+        let start = match xy.get_pivot() {
+            Some(pivot) => rect.pivot_point(pivot) + xy.as_ivec2() * pivot.axis(),
+            None => xy.as_ivec2(),
+        };
+        let mut p = start;
+        let mut filled_y = None;
+
+        for (i, line) in fmt.string.lines().enumerate() {
+            if i > 0 {
+                p = IVec2::new(min.x, p.y - 1);
+            }
+            for word in line.split_inclusive(' ') {
+                // Move the whole word to the next line if it won't fit
+                let len = word.trim_end().chars().map(glyph_width).sum::<i32>();
+                if p.x > min.x && p.x + len - 1 > max.x && len <= width {
+                    p = IVec2::new(min.x, p.y - 1);
+                }
+                for ch in word.chars() {
+                    let w = glyph_width(ch);
+                    if p.x > max.x || (p.x > min.x && p.x + w - 1 > max.x) {
+                        // Don't carry spaces over to the next line
+                        if ch == ' ' {
+                            continue;
+                        }
+                        p = IVec2::new(min.x, p.y - 1);
+                    }
+                    let lines = (start.y - p.y) as usize;
+                    if p.y < min.y || fmt.max_lines.is_some_and(|max| lines >= max) {
+                        return p;
+                    }
+                    if filled_y != Some(p.y) {
+                        self.fill_line(p, &fmt, max.x);
+                        filled_y = Some(p.y);
+                    }
+                    // A wide glyph is followed by a blank continuation tile
+                    let cont = (w > 1).then_some((p + IVec2::X, ' '));
+                    for (p, ch) in std::iter::once((p, ch)).chain(cont) {
+                        if p.cmpge(min).all() && p.cmple(max).all() && self.in_bounds(p) {
+                            fmt.write(self.get_tile_mut(p), ch);
+                        }
+                    }
+                    p.x += w;
+                }
+            }
+        }
+        p
+    }
+
+    /// Write a formatted string to the terminal, as with [`Terminal::put_string`],
+    /// but stop writing at the first glyph that would be out of bounds.
+    ///
+    /// Returns the number of tiles that didn't fit, which can be used to
+    /// detect when text has overflowed. Wide glyphs count as two tiles.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([5,1]);
+    /// let dropped = term.put_string_checked([0,0], "Hello World");
+    /// assert_eq!(6, dropped);
+    /// ```
+    pub fn put_string_checked<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+    ) -> usize {
+        let fmt = writer.formatted();
+        let mut written = 0;
+        let mut filled_y = None;
+        for (p, ch) in self.string_tiles(xy, fmt.visible()) {
+            if !self.in_bounds(p) {
+                break;
+            }
+            if filled_y != Some(p.y) {
+                self.fill_line(p, &fmt, i32::MAX);
+                filled_y = Some(p.y);
+            }
+            fmt.write(self.get_tile_mut(p), ch);
+            written += 1;
+        }
+        let len = self.string_tiles(xy, &fmt.string).count();
+        len - written
+    }
+
+    /// Write a row of glyphs to the terminal, each with it's own foreground
+    /// color, IE: for syntax highlighting.
+    ///
+    /// Glyphs are paired with colors by index and written from left to right
+    /// starting at `xy`. If the slices are different lengths only the
+    /// shorter length is written. Glyphs outside the terminal are clipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    ///
+    /// let mut term = Terminal::new([10,1]);
+    /// term.put_colored_chars([0,0], &['f', 'n'], &[Color::ORANGE, Color::ORANGE]);
+    /// ```
+    pub fn put_colored_chars(&mut self, xy: impl GridPoint, chars: &[char], fg: &[Color]) {
+        let origin = self.tiles.pivoted_point(xy);
+        for (i, (ch, color)) in chars.iter().zip(fg).enumerate() {
+            let p = origin + IVec2::new(i as i32, 0);
+            if !self.in_bounds(p) {
+                continue;
+            }
+            let t = self.get_tile_mut(p);
+            t.glyph = *ch;
+            t.fg_color = *color;
+        }
+    }
+
+    /// Write formatted text to the terminal, as with [`Terminal::put_string`].
+    ///
+    /// The text is formatted into a buffer owned by the terminal which is
+    /// reused between calls, so this won't allocate once the buffer is large
+    /// enough. Usually called via the [`term_print`](crate::term_print) macro.
+    pub fn put_fmt(&mut self, xy: impl GridPoint, args: std::fmt::Arguments) {
+        use std::fmt::Write;
+
+        let mut buffer = std::mem::take(&mut self.string_buffer);
+        buffer.clear();
+        // Writing to a string can't fail
+        let _ = buffer.write_fmt(args);
+        self.put_string(xy, buffer.as_str());
+        self.string_buffer = buffer;
+    }
+
+    /// Write a formatted string to the terminal with a drop shadow behind it.
+    ///
+    /// The string's glyphs are first written at `shadow_offset` from the
+    /// string's position with the shadow color as their foreground color,
+    /// then the string itself is written on top as with [`Terminal::put_string`].
+    /// Any part of the shadow that falls outside the terminal is clipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::{Color, IVec2};
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.put_string_shadowed([1,2], "Hello".fg(Color::WHITE), IVec2::new(1, -1), Color::DARK_GRAY);
+    /// ```
+    pub fn put_string_shadowed<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+        shadow_offset: IVec2,
+        shadow_color: Color,
+    ) {
+        let fmt = writer.formatted();
+        self.write_string(xy, shadow_offset, fmt.visible(), |t, ch| {
+            t.glyph = ch;
+            t.fg_color = shadow_color;
+        });
+        self.put_string(xy, fmt);
+    }
+
+    /// Call `write` on each tile covered by a string, along with the glyph
+    /// that would be written there. Tiles outside the terminal are skipped.
+    /// Returns the position after the last glyph, or `None` for an empty
+    /// string.
+    fn write_string(
+        &mut self,
+        xy: impl GridPoint,
+        offset: IVec2,
+        string: &str,
+        write: impl Fn(&mut Tile, char),
+    ) -> Option<IVec2> {
+        let mut end = None;
+        for (p, ch) in self.string_tiles(xy, string) {
+            let p = p + offset;
+            end = Some(p + IVec2::X);
+            if !self.in_bounds(p) {
+                continue;
+            }
+            write(self.get_tile_mut(p), ch);
+        }
+        end
+    }
+
+    /// Set the background color of a line's [`StringFormatter::fill_width`]
+    /// tiles, starting from `start` and stopping at `max_x`. Does nothing if
+    /// the string has no fill or is an overlay.
+    fn fill_line(&mut self, start: IVec2, fmt: &FormattedString, max_x: i32) {
+        if let Some((width, bg)) = fmt.fill_width.filter(|_| !fmt.overlay) {
+            let width = (width as i32).min(max_x.saturating_sub(start.x).saturating_add(1));
+            if width > 0 {
+                let rect = GridRect::from_bl(start, [width as u32, 1]);
+                self.map_region(rect, |_, t| t.bg_color = bg);
+            }
+        }
+    }
+
+    /// An iterator over the position of each glyph in a string as it would be
+    /// written to the terminal, accounting for the string's pivot, line
+    /// returns and wide glyphs. Positions are not bounds checked.
+    fn string_tiles<'s>(
+        &self,
+        xy: impl GridPoint,
+        string: &'s str,
+    ) -> impl Iterator<Item = (IVec2, char)> + 's {
         let pivot = if let Some(pivot) = xy.get_pivot() {
             Vec2::from(pivot)
         } else {
             Vec2::ZERO
         };
         let origin = self.tiles.pivoted_point(xy);
-        let fmt = writer.formatted();
-        let string = &fmt.string;
+        let width = self.width();
 
         let h = string.lines().count() as i32;
         let y = (origin.y as f32 + (h - 1) as f32 * (1.0 - pivot.y)) as i32;
 
-        let bounds = self.tiles.bounds();
+        string.lines().enumerate().flat_map(move |(i, line)| {
+            let y = y - i as i32;
+            let len = (line.chars().map(glyph_width).sum::<i32>() as usize).min(width);
+            let mut x = origin.x - (len.saturating_sub(1) as f32 * pivot.x) as i32;
+            line.chars().flat_map(move |ch| {
+                let p = IVec2::new(x, y);
+                let w = glyph_width(ch);
+                x += w;
+                // A wide glyph is followed by a blank continuation tile
+                let cont = (w > 1).then_some((p + IVec2::X, ' '));
+                std::iter::once((p, ch)).chain(cont)
+            })
+        })
+    }
 
-        //println!("Origin {}, y {}", origin, y);
+    /// An iterator over the glyphs of each row of the terminal as a string,
+    /// from the top row to the bottom.
+    ///
+    /// Trailing whitespace is trimmed from each line, leading whitespace is
+    /// kept so indentation is preserved.
+    pub fn lines(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.height()).rev().map(|y| {
+            let line: String = self.iter_row(y).map(|t| t.glyph).collect();
+            line.trim_end().to_string()
+        })
+    }
 
-        for (i, line) in string.lines().enumerate() {
-            let y = y - i as i32;
-            //println!("Origin {}, Line {}. Bounds {}", origin, y, bounds);
-            if y < bounds.min_i().y || y > bounds.max_i().y {
-                break;
+    /// Assert that the terminal's glyphs match the given text, IE: for
+    /// testing what's been drawn to a terminal.
+    ///
+    /// `expected` is compared against the terminal's [`Terminal::lines`],
+    /// from the top row to the bottom. Trailing whitespace on each line and a
+    /// single leading newline are ignored, so the text can start on the line
+    /// after the opening quote. Using a visible clear tile such as `'.'` makes
+    /// the expected text easier to read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the glyphs don't match, listing the mismatched rows.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let mut term = Terminal::new([5, 2]).with_clear_tile('.');
+    /// term.put_string([1, 0], "abc");
+    /// term.assert_looks_like(
+    ///     "
+    /// .....
+    /// .abc.",
+    /// );
+    /// ```
+    #[track_caller]
+    pub fn assert_looks_like(&self, expected: &str) {
+        let expected = expected.strip_prefix('\n').unwrap_or(expected);
+        let expected: Vec<_> = expected.lines().map(str::trim_end).collect();
+        let actual: Vec<_> = self.lines().collect();
+
+        let mut diff = String::new();
+        for i in 0..expected.len().max(actual.len()) {
+            let e = expected.get(i).copied();
+            let a = actual.get(i).map(String::as_str);
+            if e != a {
+                let y = self.height() as i32 - 1 - i as i32;
+                diff.push_str(&format!(
+                    "row {} (line {} of the text):\n  expected: {:?}\n    actual: {:?}\n",
+                    y,
+                    i + 1,
+                    e,
+                    a
+                ));
             }
+        }
+        if !diff.is_empty() {
+            panic!(
+                "Terminal doesn't look like the expected text\n{}\nterminal:\n{}",
+                diff, self
+            );
+        }
+    }
 
-            let len = line.chars().count().min(self.width());
-            let x = origin.x - ((len - 1) as f32 * pivot.x) as i32;
-            //println!("Getting index for {}, {}", x, y);
-            let i = self.transform_lti([x, y]);
-            //println!("X {}, I {}", x, i);
-            let tiles = self.tiles.slice_mut()[i..].iter_mut().take(len);
-
-            //println!("Writing string at {:?}", [x,y]);
+    /// The terminal's tiles as a flat list of cells, IE: for sending to
+    /// another rendering backend.
+    ///
+    /// Each cell is a glyph followed by its foreground and background colors
+    /// as sRGBA components. Cells are in row-major order, starting from the
+    /// bottom left of the terminal. See [`Terminal::from_cells`].
+    pub fn to_cells(&self) -> Vec<(char, [f32; 4], [f32; 4])> {
+        self.iter()
+            .map(|t| (t.glyph, t.fg_color.as_rgba_f32(), t.bg_color.as_rgba_f32()))
+            .collect()
+    }
 
-            for (char, t) in line.chars().zip(tiles) {
-                t.glyph = char;
-                fmt.apply(t);
-            }
+    /// Replace every tile in the terminal at once, IE: with a precomputed
+    /// frame.
+    ///
+    /// Tiles are in row-major order, starting from the bottom left of the
+    /// terminal. Returns an error and leaves the terminal unchanged if the
+    /// number of tiles doesn't match the terminal's size.
+    pub fn set_tiles(&mut self, tiles: &[Tile]) -> Result<(), TileCountError> {
+        let expected = self.size.len();
+        if tiles.len() != expected {
+            return Err(TileCountError {
+                expected,
+                actual: tiles.len(),
+            });
         }
+        self.tiles_mut().slice_mut().copy_from_slice(tiles);
+        Ok(())
+    }
+
+    /// Copy the terminal's text to the system clipboard, IE: for a "copy
+    /// output" button in a console.
+    ///
+    /// The text matches the terminal's [`Display`](std::fmt::Display) output.
+    #[cfg(feature = "clipboard")]
+    pub fn copy_to_clipboard(&self) -> Result<(), arboard::Error> {
+        arboard::Clipboard::new()?.set_text(self.to_string())
     }
 
     /// Clear a range of characters to the terminal's `clear_tile`.
     pub fn clear_string(&mut self, xy: impl GridPoint, len: usize) {
         let i = self.transform_lti(xy);
-        for t in self.tiles.slice_mut()[i..].iter_mut().take(len) {
-            *t = self.clear_tile;
+        let clear_tile = self.clear_tile;
+        for t in self.tiles_mut().slice_mut()[i..].iter_mut().take(len) {
+            *t = clear_tile;
         }
     }
 
@@ -367,7 +1100,7 @@ impl Terminal {
     /// Retrieve a mutable reference to a tile in the terminal.
     pub fn get_tile_mut(&mut self, xy: impl GridPoint) -> &mut Tile {
         let i = self.transform_lti(xy);
-        &mut self.tiles[i]
+        &mut self.tiles_mut()[i]
     }
 
     /// Clear an area of the terminal to the terminal's `clear_tile`.
@@ -383,8 +1116,28 @@ impl Terminal {
 
     /// Clear the terminal tiles to the terminal's `clear_tile`.
     pub fn clear(&mut self) {
-        for t in self.tiles.iter_mut() {
-            *t = self.clear_tile
+        self.fill(self.clear_tile);
+    }
+
+    /// Set every tile in the terminal to the given tile.
+    ///
+    /// Unlike writing each tile, the renderer can rebuild a filled terminal's
+    /// mesh in one pass.
+    pub fn fill(&mut self, tile: impl Into<Tile>) {
+        let tile = tile.into();
+        for t in self.tiles_mut().iter_mut() {
+            *t = tile
+        }
+        self.filled = Some(tile);
+    }
+
+    /// Reset the colors of every tile to the colors of the terminal's
+    /// `clear_tile`, without changing any glyphs.
+    pub fn reset_colors(&mut self) {
+        let clear_tile = self.clear_tile;
+        for t in self.tiles_mut().iter_mut() {
+            t.fg_color = clear_tile.fg_color;
+            t.bg_color = clear_tile.bg_color;
         }
     }
 
@@ -399,6 +1152,40 @@ impl Terminal {
         self.tiles.in_bounds(xy)
     }
 
+    /// Returns the position and tile under the cursor, IE: for showing a
+    /// tooltip.
+    ///
+    /// `to_world` should be the [`ToWorld`] component of this terminal and
+    /// `cursor_pos` is the cursor position in screen space, as returned by
+    /// `Window::cursor_position`. Returns `None` if the cursor isn't over the
+    /// terminal or the camera's viewport is unknown.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// fn tooltip(q_term: Query<(&Terminal, &ToWorld)>, q_window: Query<&Window>) {
+    ///     let (term, to_world) = q_term.single();
+    ///     let Some(cursor) = q_window.single().cursor_position() else {
+    ///         return;
+    ///     };
+    ///     if let Some((xy, tile)) = term.tile_under_cursor(to_world, cursor) {
+    ///         info!("{} at {}", tile.glyph, xy);
+    ///     }
+    /// }
+    /// ```
+    pub fn tile_under_cursor(
+        &self,
+        to_world: &ToWorld,
+        cursor_pos: Vec2,
+    ) -> Option<(IVec2, &Tile)> {
+        let world = to_world.screen_to_world(cursor_pos)?;
+        let xy = to_world.world_to_tile(world);
+        self.in_bounds(xy).then(|| (xy, self.get_tile(xy)))
+    }
+
     /// An immutable iterator over the tiles of the terminal.
     pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Tile> {
         self.tiles.iter()
@@ -406,7 +1193,7 @@ impl Terminal {
 
     /// A mutable iterator over the tiles of the terminal.
     pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut Tile> {
-        self.tiles.iter_mut()
+        self.tiles_mut().iter_mut()
     }
 
     /// An immutable iterator over an entire row of tiles in the terminal.
@@ -416,7 +1203,7 @@ impl Terminal {
 
     /// An immutable iterator over an entire row of tiles in the terminal.
     pub fn iter_row_mut(&mut self, y: usize) -> impl DoubleEndedIterator<Item = &mut Tile> {
-        self.tiles.iter_row_mut(y)
+        self.tiles_mut().iter_row_mut(y)
     }
 
     /// An immutable iterator over a range of rows in the terminal.
@@ -438,7 +1225,7 @@ impl Terminal {
         &mut self,
         range: impl RangeBounds<usize>,
     ) -> impl DoubleEndedIterator<Item = &mut [Tile]> {
-        self.tiles.iter_rows_mut(range)
+        self.tiles_mut().iter_rows_mut(range)
     }
 
     /// An immutable iterator over an entire column of tiles in the terminal.
@@ -452,25 +1239,530 @@ impl Terminal {
     ///
     /// The iterator moves from bottom to top.
     pub fn iter_column_mut(&mut self, x: usize) -> impl DoubleEndedIterator<Item = &mut Tile> {
-        self.tiles.iter_column_mut(x)
+        self.tiles_mut().iter_column_mut(x)
     }
 
-    /// Get the index for a given side on the terminal.
-    pub fn side_index(&self, side: Side) -> usize {
-        self.tiles.side_index(side)
+    /// An iterator over the tiles along the outer edge of the terminal, along
+    /// with their positions.
+    ///
+    /// Tiles are yielded in clockwise order starting from the top left. Note
+    /// this is the outermost ring of the terminal's own tiles, not its
+    /// [`Border`].
+    pub fn iter_border(&self) -> impl Iterator<Item = (IVec2, &Tile)> {
+        edge_points(self.size.as_ivec2()).map(move |p| (p, self.get_tile(p)))
     }
 
-    /// Transform a position from terminal local space (origin bottom left) to
-    /// world space (origin center).
-    #[inline]
-    pub fn transform_ltw(&self, pos: impl GridPoint) -> IVec2 {
-        pos.as_ivec2() - self.size.as_ivec2().sub(1).div(2)
+    /// Call a function on every tile in the terminal along with it's position.
+    pub fn map_tiles(&mut self, mut f: impl FnMut(IVec2, &mut Tile)) {
+        let width = self.width();
+        for (i, t) in self.tiles_mut().iter_mut().enumerate() {
+            let xy = IVec2::new((i % width) as i32, (i / width) as i32);
+            f(xy, t);
+        }
     }
 
-    /// Transform a position from world space (origin center) to terminal local
-    /// space (origin bottom left).
-    #[inline]
-    pub fn transform_wtl(&self, pos: impl GridPoint) -> IVec2 {
+    /// Set every tile in the terminal from a function of its position.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let mut term = Terminal::new([8, 8]);
+    /// // Checkerboard
+    /// term.fill_with(|p| Tile::from(if (p.x + p.y) % 2 == 0 { '#' } else { '.' }));
+    /// ```
+    pub fn fill_with(&mut self, mut f: impl FnMut(IVec2) -> Tile) {
+        self.map_tiles(|p, t| *t = f(p));
+    }
+
+    /// Call a function on every tile inside the given area of the terminal
+    /// along with it's position. The area is clipped to the terminal bounds.
+    pub fn map_region(&mut self, rect: GridRect, mut f: impl FnMut(IVec2, &mut Tile)) {
+        let min = rect.min_i().max(IVec2::ZERO);
+        let max = rect.max_i().min(self.size.as_ivec2() - 1);
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                f(IVec2::new(x, y), self.get_tile_mut([x, y]));
+            }
+        }
+    }
+
+    /// An immutable iterator over the tiles inside the given area of the
+    /// terminal.
+    ///
+    /// The area is clipped to the terminal bounds, so this won't panic if the
+    /// rect extends past the edge of the terminal. The iterator moves along
+    /// each row from left to right, starting from the bottom row.
+    pub fn iter_rect(&self, rect: GridRect) -> impl Iterator<Item = &Tile> {
+        let (xs, ys) = self.clip_rect(rect);
+        self.tiles
+            .iter_rows(ys)
+            .flat_map(move |row| row[xs.clone()].iter())
+    }
+
+    /// A mutable iterator over the tiles inside the given area of the
+    /// terminal.
+    ///
+    /// The area is clipped to the terminal bounds, so this won't panic if the
+    /// rect extends past the edge of the terminal. The iterator moves along
+    /// each row from left to right, starting from the bottom row.
+    pub fn iter_rect_mut(&mut self, rect: GridRect) -> impl Iterator<Item = &mut Tile> {
+        let (xs, ys) = self.clip_rect(rect);
+        self.tiles_mut()
+            .iter_rows_mut(ys)
+            .flat_map(move |row| row[xs.clone()].iter_mut())
+    }
+
+    /// The column and row ranges of a rect after clipping it to the terminal
+    /// bounds. The ranges are empty if the rect is entirely out of bounds.
+    fn clip_rect(&self, rect: GridRect) -> (Range<usize>, Range<usize>) {
+        let size = self.size.as_ivec2();
+        let min = rect.min_i().clamp(IVec2::ZERO, size);
+        let max = (rect.max_i() + 1).clamp(min, size);
+        (
+            min.x as usize..max.x as usize,
+            min.y as usize..max.y as usize,
+        )
+    }
+
+    /// Fill the area connected to the given position with a tile.
+    ///
+    /// Starting from `xy`, every orthogonally adjacent tile which matches the
+    /// starting tile is replaced with `tile`. [`FloodFillOptions`] controls
+    /// whether tiles must match the starting tile's colors or only its glyph.
+    ///
+    /// Does nothing if `xy` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.draw_polyline(&[[0,4].into(), [9,4].into()], Tile::from('#'));
+    /// // Fill everything below the line
+    /// term.flood_fill([0,0], Tile::from('.'), FloodFillOptions::default());
+    /// assert_eq!('.', term.get_char([9,3]));
+    /// assert_eq!(' ', term.get_char([0,5]));
+    /// ```
+    pub fn flood_fill(&mut self, xy: impl GridPoint, tile: Tile, options: FloodFillOptions) {
+        let start = xy.as_ivec2();
+        if !self.in_bounds(start) {
+            return;
+        }
+        let target = *self.get_tile(start);
+        let matches = |t: &Tile| {
+            if options.match_colors {
+                *t == target
+            } else {
+                t.same_glyph(&target)
+            }
+        };
+
+        let width = self.width();
+        let mut visited = vec![false; width * self.height()];
+        let mut stack = vec![start];
+        while let Some(p) = stack.pop() {
+            let i = p.y as usize * width + p.x as usize;
+            if visited[i] || !matches(self.get_tile(p)) {
+                continue;
+            }
+            visited[i] = true;
+            self.put_tile(p, tile);
+            for dir in [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y] {
+                let next = p + dir;
+                if self.in_bounds(next) {
+                    stack.push(next);
+                }
+            }
+        }
+    }
+
+    /// Shift every tile in a row horizontally, IE: for a scrolling ticker.
+    ///
+    /// A positive amount shifts tiles to the right, a negative amount to the
+    /// left. Tiles shifted off one end of the row wrap around to the other.
+    pub fn shift_row(&mut self, row: usize, by: i32) {
+        let width = self.width();
+        let by = by.rem_euclid(width as i32) as usize;
+        if let Some(row) = self.iter_rows_mut(row..=row).next() {
+            row.rotate_right(by);
+        }
+    }
+
+    /// Shift every tile in a column vertically.
+    ///
+    /// A positive amount shifts tiles up, a negative amount down. Tiles
+    /// shifted off one end of the column wrap around to the other.
+    pub fn shift_column(&mut self, column: usize, by: i32) {
+        let height = self.height();
+        let by = by.rem_euclid(height as i32) as usize;
+        let mut tiles: Vec<_> = self.iter_column(column).copied().collect();
+        tiles.rotate_right(by);
+        for (t, shifted) in self.iter_column_mut(column).zip(tiles) {
+            *t = shifted;
+        }
+    }
+
+    /// Swap the foreground and background colors of every tile in the given
+    /// area, IE: to show a selection. Glyphs are unaffected and inverting
+    /// the same area twice restores the original colors. The area is clipped
+    /// to the terminal bounds.
+    pub fn invert_rect(&mut self, rect: GridRect) {
+        self.map_region(rect, |_, t| {
+            std::mem::swap(&mut t.fg_color, &mut t.bg_color)
+        });
+    }
+
+    /// Draw a vertical bar filling the given area from the bottom up, IE: for
+    /// a vertical health bar or audio meter.
+    ///
+    /// `fraction` is clamped to `0.0..=1.0`. The top of the bar is drawn with
+    /// sub-tile precision using the partial block glyphs `▁▂▃▄▅▆▇`. Only `▄`
+    /// and `█` are part of code page 437, the others require a font with a
+    /// custom [`UvMapping`](crate::UvMapping) and will otherwise be drawn as
+    /// a space. Every tile in the area is set to the given colors. Any part
+    /// of the area outside the terminal is clipped.
+    pub fn draw_vertical_bar(&mut self, rect: GridRect, fraction: f32, fg: Color, bg: Color) {
+        let bottom = rect.min_i().y;
+        let filled = fraction.clamp(0.0, 1.0) * rect.size().y as f32;
+        let full = filled.floor() as i32;
+        let eighths = ((filled - full as f32) * 8.0).round() as usize;
+        self.map_region(rect, |p, t| {
+            let y = p.y - bottom;
+            t.glyph = match y.cmp(&full) {
+                Ordering::Less => '█',
+                Ordering::Equal => VERTICAL_BLOCKS[eighths],
+                Ordering::Greater => ' ',
+            };
+            t.fg_color = fg;
+            t.bg_color = bg;
+        });
+    }
+
+    /// Draw a grid of lines across the whole terminal, IE: for a graph paper
+    /// background in a map editor.
+    ///
+    /// Every tile where `x % spacing.x == 0` or `y % spacing.y == 0` has its
+    /// glyph and foreground color set. Other tiles and all background colors
+    /// are left untouched. A spacing of `1` fills the entire terminal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either axis of `spacing` is zero.
+    pub fn draw_grid(&mut self, spacing: impl Size2d, glyph: char, color: Color) {
+        let spacing = spacing.as_ivec2();
+        assert!(
+            spacing.cmpgt(IVec2::ZERO).all(),
+            "Grid spacing must be non-zero, got {}",
+            spacing
+        );
+        let rect = GridRect::from_bl([0, 0], self.size);
+        self.map_region(rect, |p, t| {
+            if p.x % spacing.x == 0 || p.y % spacing.y == 0 {
+                t.glyph = glyph;
+                t.fg_color = color;
+            }
+        });
+    }
+
+    /// Fill an area of the terminal with a checkerboard of two tiles, IE: to
+    /// show transparent areas in an editor.
+    ///
+    /// The pattern alternates between `a` and `b` in blocks of `cell` by
+    /// `cell` tiles, starting with `a` at the bottom left of `rect`. The area
+    /// is clipped to the terminal bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cell` is zero.
+    pub fn checker_fill(&mut self, rect: GridRect, a: Tile, b: Tile, cell: u32) {
+        assert!(cell > 0, "Checker cell size must be non-zero");
+        let origin = rect.min_i();
+        let cell = cell as i32;
+        self.map_region(rect, |p, t| {
+            let block = (p - origin) / cell;
+            *t = if (block.x + block.y) % 2 == 0 { a } else { b };
+        });
+    }
+
+    /// Write every code page 437 glyph in a 16x16 grid, in the same layout
+    /// as a font image, IE: for checking a new font.
+    ///
+    /// `start` is the top left corner of the grid. Glyph index `i` is written
+    /// `i % 16` tiles to the right and `i / 16` tiles down from `start`. Any
+    /// part of the grid outside the terminal is clipped.
+    pub fn draw_glyph_palette(&mut self, start: IVec2) {
+        for i in 0..=255u8 {
+            let p = start + IVec2::new(i as i32 % 16, -(i as i32 / 16));
+            if self.in_bounds(p) {
+                self.get_tile_mut(p).glyph = code_page_437::index_to_glyph(i);
+            }
+        }
+    }
+
+    /// Shuffle the glyphs inside the given area of the terminal, leaving the
+    /// colors of each tile in place. The area is clipped to the terminal
+    /// bounds.
+    ///
+    /// The shuffle is deterministic - the same seed will always produce the
+    /// same result for the same glyphs.
+    pub fn scramble_glyphs(&mut self, rect: GridRect, seed: u64) {
+        let mut glyphs = Vec::new();
+        self.map_region(rect, |_, t| glyphs.push(t.glyph));
+
+        let mut state = seed;
+        for i in (1..glyphs.len()).rev() {
+            let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+            glyphs.swap(i, j);
+        }
+
+        let mut glyphs = glyphs.into_iter();
+        self.map_region(rect, |_, t| {
+            if let Some(glyph) = glyphs.next() {
+                t.glyph = glyph;
+            }
+        });
+    }
+
+    /// Remap the colors of every tile in the terminal, IE: when switching
+    /// between color themes.
+    ///
+    /// Each `(from, to)` pair replaces any foreground or background color
+    /// exactly equal to `from` with `to`. Colors not in the palette are left
+    /// unchanged. Since colors can't be hashed, the palette is a slice of
+    /// pairs rather than a map.
+    pub fn apply_palette(&mut self, palette: &[(Color, Color)]) {
+        let remap = |color: Color| {
+            palette
+                .iter()
+                .find(|(from, _)| *from == color)
+                .map_or(color, |(_, to)| *to)
+        };
+        for t in self.tiles_mut().iter_mut() {
+            t.fg_color = remap(t.fg_color);
+            t.bg_color = remap(t.bg_color);
+        }
+    }
+
+    /// Draw a path of connected line segments between consecutive points.
+    ///
+    /// Each segment is drawn with Bresenham's line algorithm. Points where
+    /// segments meet are only written once, and any part of the path outside
+    /// the terminal is clipped.
+    pub fn draw_polyline(&mut self, points: &[IVec2], tile: Tile) {
+        for p in polyline_points(points) {
+            if self.in_bounds(p) {
+                self.put_tile(p, tile);
+            }
+        }
+    }
+
+    /// Fill the triangle between three points.
+    ///
+    /// A tile is filled if its position lies inside the triangle or exactly on
+    /// one of its edges. If the points are collinear only the line between
+    /// them is drawn. Any part of the triangle outside the terminal is
+    /// clipped.
+    pub fn fill_triangle(&mut self, a: IVec2, b: IVec2, c: IVec2, tile: Tile) {
+        // Twice the signed area of the triangle pqr
+        let edge = |p: IVec2, q: IVec2, r: IVec2| (q - p).perp_dot(r - p);
+        let area = edge(a, b, c);
+        if area == 0 {
+            self.draw_polyline(&[a, b, c], tile);
+            return;
+        }
+        let min = a.min(b).min(c).max(IVec2::ZERO);
+        let max = a.max(b).max(c).min(self.size.as_ivec2() - 1);
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let p = IVec2::new(x, y);
+                let weights = [edge(b, c, p), edge(c, a, p), edge(a, b, p)];
+                if weights.iter().all(|w| w * area.signum() >= 0) {
+                    self.put_tile(p, tile);
+                }
+            }
+        }
+    }
+
+    /// Draw part of a circle's outline, IE: for a radial cooldown indicator.
+    ///
+    /// Angles are in degrees, counter-clockwise from the positive x axis, and
+    /// the arc is drawn counter-clockwise from `start_deg` to `end_deg`. An
+    /// arc spanning 360 degrees or more draws the full circle. A radius of
+    /// zero draws a single tile at `center`. Any part of the arc outside the
+    /// terminal is clipped.
+    pub fn draw_arc(
+        &mut self,
+        center: IVec2,
+        radius: u32,
+        start_deg: f32,
+        end_deg: f32,
+        tile: Tile,
+    ) {
+        let sweep = end_deg - start_deg;
+        let full = sweep >= 360.0;
+        let sweep = sweep.rem_euclid(360.0);
+        for p in circle_points(center, radius) {
+            let d = (p - center).as_vec2();
+            let angle = d.y.atan2(d.x).to_degrees();
+            let in_arc = full || d == Vec2::ZERO || (angle - start_deg).rem_euclid(360.0) <= sweep;
+            if in_arc && self.in_bounds(p) {
+                self.put_tile(p, tile);
+            }
+        }
+    }
+
+    /// Draw a path of line segments using arrow glyphs pointing from each
+    /// point to the next, IE: for previewing a movement path.
+    ///
+    /// Only the glyph and foreground color of each tile is changed. The last
+    /// point of the path is drawn with a `•` to mark the destination. Code
+    /// Page 437 has no diagonal arrows, so diagonal steps use `/` and `\`.
+    /// Any part of the path outside the terminal is clipped.
+    pub fn draw_path_arrows(&mut self, points: &[IVec2], color: Color) {
+        let path: Vec<_> = polyline_points(points).collect();
+        for (i, p) in path.iter().enumerate() {
+            if !self.in_bounds(*p) {
+                continue;
+            }
+            let glyph = match path.get(i + 1) {
+                Some(next) => arrow_glyph(*next - *p),
+                None => '•',
+            };
+            let t = self.get_tile_mut(*p);
+            t.glyph = glyph;
+            t.fg_color = color;
+        }
+    }
+
+    /// Draw a box using the glyphs from a [`Border`]. `xy` is the bottom left
+    /// corner of the box and `size` includes the box's edges.
+    ///
+    /// If `merge` is true, any edge or corner that lands on an existing box
+    /// drawing glyph of the same line style is combined with it to form the
+    /// correct junction (`├`, `┬`, `┼`, etc), so adjacent boxes can share
+    /// edges. Otherwise existing glyphs are overwritten.
+    ///
+    /// The border's edge colors are applied if set, the inside of the box and
+    /// any border strings are ignored. Any part of the box outside the
+    /// terminal is clipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([7,3]);
+    /// term.draw_box([0,0], [4,3], &Border::single_line(), true);
+    /// term.draw_box([3,0], [4,3], &Border::single_line(), true);
+    /// assert_eq!("┌──┬──┐", term.get_string([0,2], 7));
+    /// ```
+    pub fn draw_box(
+        &mut self,
+        xy: impl GridPoint,
+        size: impl Size2d,
+        border: &Border,
+        merge: bool,
+    ) {
+        let origin = xy.as_ivec2();
+        let size = size.as_ivec2();
+        if size.cmplt(IVec2::splat(2)).any() {
+            return;
+        }
+        let max = size - 1;
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let glyph = match [x, y] {
+                    [0, 0] => border.bottom_left,
+                    [0, y] if y == max.y => border.top_left,
+                    [x, 0] if x == max.x => border.bottom_right,
+                    [x, y] if x == max.x && y == max.y => border.top_right,
+                    [0, _] => border.left,
+                    [x, _] if x == max.x => border.right,
+                    [_, 0] => border.bottom,
+                    [_, y] if y == max.y => border.top,
+                    _ => continue,
+                };
+                let p = origin + IVec2::new(x, y);
+                if !self.in_bounds(p) {
+                    continue;
+                }
+                let t = self.get_tile_mut(p);
+                t.glyph = if merge {
+                    merge_box_glyphs(t.glyph, glyph)
+                } else {
+                    glyph
+                };
+                if let Some(fg) = border.edge_fg_color {
+                    t.fg_color = fg;
+                }
+                if let Some(bg) = border.edge_bg_color {
+                    t.bg_color = bg;
+                }
+            }
+        }
+    }
+
+    /// Count the number of tiles in the terminal with the given glyph.
+    pub fn count_glyph(&self, glyph: char) -> usize {
+        self.iter().filter(|t| t.glyph == glyph).count()
+    }
+
+    /// Find the position of the first tile with the given glyph.
+    ///
+    /// Tiles are searched in row-major order, starting from the bottom left.
+    pub fn find_glyph(&self, glyph: char) -> Option<IVec2> {
+        self.positions_of_glyph(glyph).next()
+    }
+
+    /// An iterator over the positions of every tile with the given glyph.
+    ///
+    /// Tiles are searched in row-major order, starting from the bottom left.
+    pub fn positions_of_glyph(&self, glyph: char) -> impl Iterator<Item = IVec2> + '_ {
+        self.iter()
+            .enumerate()
+            .filter(move |(_, t)| t.glyph == glyph)
+            .map(|(i, _)| self.transform_itl(i))
+    }
+
+    /// Returns the position and value of every tile which differs from the
+    /// same tile in `prev`, in row-major order.
+    ///
+    /// If the terminals are different sizes then every tile will be returned.
+    pub fn diff(&self, prev: &Terminal) -> Vec<(IVec2, Tile)> {
+        if self.size != prev.size {
+            return self
+                .iter()
+                .enumerate()
+                .map(|(i, t)| (self.transform_itl(i), *t))
+                .collect();
+        }
+        self.iter()
+            .zip(prev.iter())
+            .enumerate()
+            .filter(|(_, (curr, prev))| curr != prev)
+            .map(|(i, (t, _))| (self.transform_itl(i), *t))
+            .collect()
+    }
+
+    /// Get the index for a given side on the terminal.
+    pub fn side_index(&self, side: Side) -> usize {
+        self.tiles.side_index(side)
+    }
+
+    /// Transform a position from terminal local space (origin bottom left) to
+    /// world space (origin center).
+    #[inline]
+    pub fn transform_ltw(&self, pos: impl GridPoint) -> IVec2 {
+        pos.as_ivec2() - self.size.as_ivec2().sub(1).div(2)
+    }
+
+    /// Transform a position from world space (origin center) to terminal local
+    /// space (origin bottom left).
+    #[inline]
+    pub fn transform_wtl(&self, pos: impl GridPoint) -> IVec2 {
         //println!("P {}, Half size {}", pos.as_ivec2(),  self.size.as_ivec2().sub(1).div(2));
         pos.as_ivec2() + self.size.as_ivec2().div(2)
     }
@@ -480,7 +1772,7 @@ impl Terminal {
     }
 
     pub fn slice_mut(&mut self) -> &mut [Tile] {
-        self.tiles.slice_mut()
+        self.tiles_mut().slice_mut()
     }
 
     pub fn bounds_with_border(&self) -> GridRect {
@@ -500,6 +1792,246 @@ impl Terminal {
     }
 }
 
+/// Write formatted text to a terminal without allocating a new string.
+///
+/// Takes a terminal, a position and the same arguments as [`format!`]. See
+/// [`Terminal::put_fmt`].
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ascii_terminal::*;
+///
+/// let mut term = Terminal::new([20, 3]);
+/// let (hp, max) = (7, 10);
+/// term_print!(term, [0, 0], "HP: {}/{}", hp, max);
+/// assert_eq!("HP: 7/10", term.get_string([0, 0], 8));
+/// ```
+#[macro_export]
+macro_rules! term_print {
+    ($term:expr, $xy:expr, $($arg:tt)*) => {
+        $term.put_fmt($xy, format_args!($($arg)*))
+    };
+}
+
+/// The number of columns a glyph takes up when written as part of a string.
+/// CJK and other full width glyphs take up two, everything else one.
+fn glyph_width(ch: char) -> i32 {
+    match ch as u32 {
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// A simple deterministic hash used to generate a sequence of pseudo-random
+/// numbers from a seed.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Every point on the outline of a circle, using the midpoint circle
+/// algorithm. Each point is only returned once.
+fn circle_points(center: IVec2, radius: u32) -> Vec<IVec2> {
+    let r = radius as i32;
+    let mut points = Vec::new();
+    let (mut x, mut y) = (r, 0);
+    let mut err = 1 - r;
+    while x >= y {
+        for (dx, dy) in [(x, y), (y, x)] {
+            for (sx, sy) in [(1, 1), (-1, 1), (1, -1), (-1, -1)] {
+                points.push(center + IVec2::new(dx * sx, dy * sy));
+            }
+        }
+        y += 1;
+        if err < 0 {
+            err += 2 * y + 1;
+        } else {
+            x -= 1;
+            err += 2 * (y - x) + 1;
+        }
+    }
+    points.sort_by_key(|p| (p.y, p.x));
+    points.dedup();
+    points
+}
+
+/// Every point on a line from `a` to `b`, inclusive.
+fn line_points(a: IVec2, b: IVec2) -> impl Iterator<Item = IVec2> {
+    let d = (b - a).abs();
+    let step = (b - a).signum();
+    let mut err = d.x - d.y;
+    let mut next = Some(a);
+    std::iter::from_fn(move || {
+        let curr = next?;
+        next = if curr == b {
+            None
+        } else {
+            let mut p = curr;
+            let e2 = err * 2;
+            if e2 > -d.y {
+                err -= d.y;
+                p.x += step.x;
+            }
+            if e2 < d.x {
+                err += d.x;
+                p.y += step.y;
+            }
+            Some(p)
+        };
+        Some(curr)
+    })
+}
+
+/// Block glyphs filling a tile from the bottom, in eighths.
+const VERTICAL_BLOCKS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The glyph pointing in the direction of a single step along a path.
+fn arrow_glyph(dir: IVec2) -> char {
+    match dir.signum().to_array() {
+        [1, 0] => '→',
+        [-1, 0] => '←',
+        [0, 1] => '↑',
+        [0, -1] => '↓',
+        [1, 1] | [-1, -1] => '/',
+        [1, -1] | [-1, 1] => '\\',
+        _ => '•',
+    }
+}
+
+/// The points along the edge of a rect of the given size with its bottom
+/// left at the origin, in clockwise order starting from the top left. Each
+/// point is only returned once.
+fn edge_points(size: IVec2) -> impl Iterator<Item = IVec2> {
+    let max = size - 1;
+    let top = (0..=max.x).map(move |x| IVec2::new(x, max.y));
+    let right = (0..max.y).rev().map(move |y| IVec2::new(max.x, y));
+    // A single row or column is already covered by the top and right edges
+    let bottom = if max.y > 0 { 0..max.x } else { 0..0 };
+    let bottom = bottom.rev().map(|x| IVec2::new(x, 0));
+    let left = if max.x > 0 { 1..max.y } else { 0..0 };
+    let left = left.map(|y| IVec2::new(0, y));
+    top.chain(right).chain(bottom).chain(left)
+}
+
+/// Box drawing glyphs along with the directions they connect to, as north,
+/// east, south and west bits.
+#[rustfmt::skip]
+const BOX_CONNECTIONS: [[(char, u8); 11]; 2] = [
+    [
+        ('─', 0b1010), ('│', 0b0101), ('┌', 0b0110), ('┐', 0b1100),
+        ('└', 0b0011), ('┘', 0b1001), ('├', 0b0111), ('┤', 0b1101),
+        ('┬', 0b1110), ('┴', 0b1011), ('┼', 0b1111),
+    ],
+    [
+        ('═', 0b1010), ('║', 0b0101), ('╔', 0b0110), ('╗', 0b1100),
+        ('╚', 0b0011), ('╝', 0b1001), ('╠', 0b0111), ('╣', 0b1101),
+        ('╦', 0b1110), ('╩', 0b1011), ('╬', 0b1111),
+    ],
+];
+
+/// Combine two box drawing glyphs into a single junction glyph. If the
+/// glyphs aren't both box drawing glyphs of the same line style, `new` is
+/// returned.
+fn merge_box_glyphs(existing: char, new: char) -> char {
+    for set in BOX_CONNECTIONS.iter() {
+        let bits = |c: char| set.iter().find(|(g, _)| *g == c).map(|(_, b)| *b);
+        if let (Some(a), Some(b)) = (bits(existing), bits(new)) {
+            let merged = a | b;
+            return set
+                .iter()
+                .find(|(_, b)| *b == merged)
+                .map_or(new, |(g, _)| *g);
+        }
+    }
+    new
+}
+
+/// Every point along a path of line segments. Points shared by consecutive
+/// segments are only returned once.
+fn polyline_points(points: &[IVec2]) -> impl Iterator<Item = IVec2> + '_ {
+    points.first().copied().into_iter().chain(
+        points
+            .windows(2)
+            .flat_map(|seg| line_points(seg[0], seg[1]).skip(1)),
+    )
+}
+
+/// Access a tile in the terminal via it's position.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ascii_terminal::*;
+///
+/// let mut term = Terminal::new([10,10]);
+/// term[[1,1]] = Tile::from('x');
+/// assert_eq!('x', term[[1,1]].glyph);
+/// ```
+impl Index<[i32; 2]> for Terminal {
+    type Output = Tile;
+
+    fn index(&self, xy: [i32; 2]) -> &Self::Output {
+        debug_assert!(
+            self.wrap_addressing || self.in_bounds(xy),
+            "Terminal index {:?} is out of bounds for terminal size {}",
+            xy,
+            self.size
+        );
+        self.get_tile(xy)
+    }
+}
+
+impl IndexMut<[i32; 2]> for Terminal {
+    fn index_mut(&mut self, xy: [i32; 2]) -> &mut Self::Output {
+        debug_assert!(
+            self.wrap_addressing || self.in_bounds(xy),
+            "Terminal index {:?} is out of bounds for terminal size {}",
+            xy,
+            self.size
+        );
+        self.get_tile_mut(xy)
+    }
+}
+
+/// Formats the terminal's glyphs as text, one line per row from the top row
+/// to the bottom. Trailing whitespace is trimmed from each line.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ascii_terminal::*;
+///
+/// let mut term = Terminal::new([5, 2]);
+/// term.put_string([0, 1], "Hello");
+/// assert_eq!("Hello\n", term.to_string());
+/// ```
+impl std::fmt::Display for Terminal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, line) in self.lines().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            f.write_str(&line)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -518,6 +2050,12 @@ mod tests {
         let t = term.get_tile([1, 2]);
         assert_eq!('q', t.glyph);
         assert_eq!(Color::RED, t.fg_color);
+
+        term.put_char([0, 0], 'H'.fg(Color::GREEN).bg(Color::YELLOW));
+        let t = term.get_tile([0, 0]);
+        assert_eq!('H', t.glyph);
+        assert_eq!(Color::GREEN, t.fg_color);
+        assert_eq!(Color::YELLOW, t.bg_color);
     }
 
     #[test]
@@ -529,4 +2067,963 @@ mod tests {
         term.put_string([1, 1], "Hello");
         assert_eq!("He", term.get_string([1, 1], 2));
     }
+
+    #[test]
+    fn append_string() {
+        let mut term = Terminal::new([10, 3]);
+        let end = term.append_string([0, 0], "HP: ");
+        assert_eq!(IVec2::new(4, 0), end);
+        let end = term.append_string(end, "100");
+        assert_eq!(IVec2::new(7, 0), end);
+        assert_eq!("HP: 100", term.get_string([0, 0], 7));
+
+        // The end of the last line of a multi-line string
+        assert_eq!(IVec2::new(2, 1), term.append_string([0, 1], "abc\nde"));
+        assert_eq!(IVec2::new(3, 2), term.append_string([3, 2], ""));
+    }
+
+    #[test]
+    fn wide_glyphs() {
+        let mut term = Terminal::new([6, 2]);
+        let end = term.append_string([0, 1], "中a");
+        assert_eq!(IVec2::new(3, 1), end);
+        assert_eq!("中 a", term.get_string([0, 1], 3));
+
+        // A wide glyph which doesn't fit in the last column wraps
+        let rect = GridRect::from_bl([0, 0], [4u32, 2]);
+        let mut term = Terminal::new([4, 2]);
+        term.put_string_in(rect, [0, 1], "abc中");
+        term.assert_looks_like(
+            "
+abc
+中",
+        );
+        let mut term = Terminal::new([4, 2]);
+        term.put_string_in(rect, [0, 1], "ab中文");
+        term.assert_looks_like(
+            "
+ab中
+文",
+        );
+
+        let mut term = Terminal::new([3, 1]);
+        assert_eq!(2, term.put_string_checked([0, 0], "a中文"));
+    }
+
+    #[test]
+    fn put_colored_chars() {
+        let mut term = Terminal::new([4, 1]);
+        let gradient = [Color::BLACK, Color::GRAY, Color::WHITE];
+        term.put_colored_chars([0, 0], &['a', 'b', 'c'], &gradient);
+        assert_eq!("abc", term.get_string([0, 0], 3));
+        for (i, color) in gradient.iter().enumerate() {
+            assert_eq!(*color, term.get_tile([i as i32, 0]).fg_color);
+        }
+
+        // Writes up to the shorter slice and clips at the edge
+        let mut term = Terminal::new([4, 1]);
+        term.put_colored_chars([2, 0], &['x', 'y', 'z'], &gradient[..2]);
+        assert_eq!("  xy", term.get_string([0, 0], 4));
+        term.put_colored_chars([3, 0], &['q', 'r'], &gradient);
+        assert_eq!("  xq", term.get_string([0, 0], 4));
+    }
+
+    #[test]
+    fn reset_colors() {
+        let mut term = Terminal::new([5, 1]);
+        term.set_clear_tile(Tile {
+            glyph: '.',
+            fg_color: Color::GREEN,
+            bg_color: Color::BLUE,
+        });
+        term.put_string([0, 0], "abc".fg(Color::RED).bg(Color::YELLOW));
+
+        term.reset_colors();
+        assert_eq!("abc..", term.get_string([0, 0], 5));
+        for t in term.iter() {
+            assert_eq!(Color::GREEN, t.fg_color);
+            assert_eq!(Color::BLUE, t.bg_color);
+        }
+    }
+
+    #[test]
+    fn put_string_in() {
+        let mut term = Terminal::new([10, 5]);
+        let rect = GridRect::from_bl([2, 0], [5u32, 4]);
+        let end = term.put_string_in(rect, [2, 3], "hello big world abcdefgh");
+
+        assert_eq!("hello", term.get_string([2, 3], 5));
+        assert_eq!("big  ", term.get_string([2, 2], 5));
+        assert_eq!("world", term.get_string([2, 1], 5));
+        // Words longer than the rect are split, anything below is dropped
+        assert_eq!("abcde", term.get_string([2, 0], 5));
+        assert_eq!(IVec2::new(2, -1), end);
+        assert_eq!(IVec2::new(6, 1), term.put_string_in(rect, [2, 1], "done"));
+
+        // Wrapped lines count towards the line limit
+        let mut limited = Terminal::new([10, 5]);
+        let end = limited.put_string_in(rect, [2, 3], "one two six ten map".max_lines(3));
+        assert_eq!(
+            ["", "  one", "  two", "  six", ""],
+            limited.lines().collect::<Vec<_>>()[..]
+        );
+        assert_eq!(IVec2::new(2, 0), end);
+
+        // Nothing is written outside the rect
+        for y in 0..5 {
+            assert_eq!(' ', term.get_char([1, y]));
+            assert_eq!(' ', term.get_char([7, y]));
+        }
+        assert_eq!("     ", term.get_string([2, 4], 5));
+    }
+
+    #[test]
+    fn put_string_in_pivot() {
+        let mut term = Terminal::new([10, 5]);
+        let rect = GridRect::from_bl([2, 0], [5u32, 4]);
+        // Pivoted positions are relative to the rect, not the terminal
+        term.put_string_in(rect, [1, 0].pivot(Pivot::TopLeft), "hey you");
+        assert_eq!("hey", term.get_string([3, 3], 3));
+        assert_eq!("you", term.get_string([2, 2], 3));
+    }
+
+    #[test]
+    fn put_string_checked() {
+        let mut term = Terminal::new([5, 2]);
+        assert_eq!(0, term.put_string_checked([0, 1], "Hi"));
+
+        assert_eq!(6, term.put_string_checked([0, 0], "Hello World"));
+        assert_eq!("Hello", term.get_string([0, 0], 5));
+
+        // Writing stops at the first glyph that doesn't fit, even if later
+        // lines would
+        assert_eq!(4, term.put_string_checked([0, 0], "abcdefg\nxy"));
+        assert_eq!("abcde", term.get_string([0, 1], 5));
+        assert_eq!("Hello", term.get_string([0, 0], 5));
+    }
+
+    #[test]
+    fn transparent_clear_tile() {
+        let mut term = Terminal::new([5, 5]).with_clear_tile(Tile::transparent());
+        assert!(term.iter().all(|t| t.bg_color.a() == 0.0));
+
+        term.put_char([1, 1], 'a'.bg(Color::RED));
+        term.clear();
+        assert_eq!(0.0, term.get_tile([1, 1]).bg_color.a());
+
+        term.resize([8, 8]);
+        assert!(term.iter().all(|t| t.bg_color.a() == 0.0));
+    }
+
+    #[test]
+    fn set_tiles() {
+        let mut term = Terminal::new([3, 2]);
+        term.take_dirty();
+        let tiles: Vec<_> = "abcdef".chars().map(Tile::from).collect();
+        term.set_tiles(&tiles).unwrap();
+        assert_eq!("def\nabc", term.to_string());
+        assert!(term.take_dirty());
+
+        let err = term.set_tiles(&tiles[..4]).unwrap_err();
+        assert_eq!(6, err.expected);
+        assert_eq!(4, err.actual);
+        assert_eq!("def\nabc", term.to_string());
+        assert!(!term.is_dirty());
+    }
+
+    #[test]
+    fn try_new() {
+        assert!(Terminal::try_new([5, 3]).is_ok());
+        assert_eq!(
+            Err(TerminalSizeError {
+                size: UVec2::new(0, 3)
+            }),
+            Terminal::try_new([0, 3]).map(|t| t.size())
+        );
+        assert!(Terminal::try_new([5, 0]).is_err());
+    }
+
+    #[test]
+    fn resize_clamp() {
+        let mut term = Terminal::new([5, 5]);
+        term.resize([3, 2]);
+        assert_eq!(UVec2::new(3, 2), term.size());
+
+        term.resize([0, 4]);
+        assert_eq!(UVec2::new(1, 4), term.size());
+        assert_eq!(4, term.iter().count());
+    }
+
+    #[test]
+    fn new_clamp() {
+        let mut term = Terminal::new([0, 0]);
+        assert_eq!(UVec2::ONE, term.size());
+        // Wrapping and shifting would divide by zero on an empty terminal
+        term.wrap_addressing = true;
+        term.put_char([3, -2], 'a');
+        term.shift_row(0, 1);
+        term.shift_column(0, 1);
+        assert_eq!('a', term.get_char([0, 0]));
+    }
+
+    #[test]
+    fn resize_anchored() {
+        let mut term = Terminal::new([3, 3]);
+        term.put_char([1, 1], 'c');
+        term.put_char([0, 2], 'a');
+
+        term.resize_anchored([5, 5], Pivot::Center);
+        assert_eq!(UVec2::new(5, 5), term.size());
+        assert_eq!('c', term.get_char([2, 2]));
+        assert_eq!('a', term.get_char([1, 3]));
+        assert_eq!(2, term.iter().filter(|t| t.glyph != ' ').count());
+
+        // Shrinking back to the original size restores the original layout
+        term.resize_anchored([3, 3], Pivot::Center);
+        assert_eq!('c', term.get_char([1, 1]));
+        assert_eq!('a', term.get_char([0, 2]));
+
+        term.resize_anchored([4, 6], Pivot::TopLeft);
+        assert_eq!('a', term.get_char([0, 5]));
+        assert_eq!('c', term.get_char([1, 4]));
+    }
+
+    #[test]
+    fn set_clear_tile() {
+        let mut term = Terminal::new([5, 5]);
+        term.put_char([1, 1], 'a'.bg(Color::RED));
+
+        term.set_clear_tile(Tile {
+            bg_color: Color::BLUE,
+            ..Default::default()
+        });
+
+        assert_eq!(Color::BLUE, term.get_tile([0, 0]).bg_color);
+        assert_eq!(Color::BLUE, term.get_tile([4, 4]).bg_color);
+        assert_eq!('a', term.get_char([1, 1]));
+        assert_eq!(Color::RED, term.get_tile([1, 1]).bg_color);
+    }
+
+    #[test]
+    fn draw_polyline() {
+        let path = [IVec2::new(1, 1), IVec2::new(4, 1), IVec2::new(4, 3)];
+        let points: Vec<_> = polyline_points(&path).collect();
+        assert_eq!(6, points.len());
+        assert_eq!(1, points.iter().filter(|p| **p == path[1]).count());
+
+        let mut term = Terminal::new([5, 5]);
+        term.draw_polyline(&path, Tile::from('#'));
+        assert_eq!(6, term.count_glyph('#'));
+        assert_eq!('#', term.get_char([4, 1]));
+        assert_eq!('#', term.get_char([4, 3]));
+
+        // Out of bounds segments are clipped
+        term.draw_polyline(&[IVec2::new(-2, 0), IVec2::new(2, 0)], Tile::from('x'));
+        assert_eq!(3, term.count_glyph('x'));
+    }
+
+    #[test]
+    fn fill_triangle() {
+        let mut term = Terminal::new([8, 8]);
+        let tile = Tile::from('#');
+        term.fill_triangle(IVec2::new(0, 0), IVec2::new(4, 0), IVec2::new(0, 4), tile);
+        // 5 + 4 + 3 + 2 + 1
+        assert_eq!(15, term.count_glyph('#'));
+        assert_eq!('#', term.get_char([2, 2]));
+        assert_eq!(' ', term.get_char([3, 2]));
+
+        // Winding order doesn't matter
+        term.clear();
+        term.fill_triangle(IVec2::new(0, 4), IVec2::new(4, 0), IVec2::new(0, 0), tile);
+        assert_eq!(15, term.count_glyph('#'));
+
+        // Collinear points draw a line
+        term.clear();
+        term.fill_triangle(IVec2::new(0, 0), IVec2::new(2, 2), IVec2::new(4, 4), tile);
+        assert_eq!(5, term.count_glyph('#'));
+
+        // Clipped to the terminal
+        term.clear();
+        term.fill_triangle(IVec2::new(-4, 0), IVec2::new(4, 0), IVec2::new(-4, 8), tile);
+        assert_eq!(15, term.count_glyph('#'));
+    }
+
+    #[test]
+    fn draw_arc() {
+        let center = IVec2::new(7, 7);
+        let mut term = Terminal::new([15, 15]);
+        term.draw_arc(center, 5, 0.0, 360.0, Tile::from('o'));
+        let circle = term.count_glyph('o');
+        assert_eq!(circle_points(center, 5).len(), circle);
+        assert_eq!('o', term.get_char([12, 7]));
+        assert_eq!('o', term.get_char([2, 7]));
+
+        term.clear();
+        term.draw_arc(center, 5, 0.0, 90.0, Tile::from('x'));
+        let points: Vec<_> = term.positions_of_glyph('x').collect();
+        assert!(points.iter().all(|p| p.x >= center.x && p.y >= center.y));
+        assert!(points.contains(&IVec2::new(12, 7)));
+        assert!(points.contains(&IVec2::new(7, 12)));
+        // A quarter circle shares its two end points with the neighbouring
+        // quarters
+        assert_eq!((circle + 4) / 4, points.len());
+
+        // Arcs wrap around through 0 degrees
+        term.clear();
+        term.draw_arc(center, 5, 270.0, 450.0, Tile::from('x'));
+        assert!(term.positions_of_glyph('x').all(|p| p.x >= center.x));
+
+        term.clear();
+        term.draw_arc(center, 0, 0.0, 90.0, Tile::from('x'));
+        assert_eq!(1, term.count_glyph('x'));
+        assert_eq!('x', term.get_char(center));
+
+        // Clipped to the terminal
+        term.clear();
+        term.draw_arc(IVec2::ZERO, 3, 0.0, 360.0, Tile::from('x'));
+        assert!(term.count_glyph('x') > 0);
+    }
+
+    #[test]
+    fn checker_fill() {
+        let mut term = Terminal::new([5, 4]).with_clear_tile('.');
+        let a = Tile::from('o');
+        let b = Tile::from('#');
+        term.checker_fill(GridRect::from_bl([1, 1], [3u32, 2]), a, b, 1);
+        term.assert_looks_like(
+            "
+.....
+.#o#.
+.o#o.
+.....",
+        );
+
+        let mut term = Terminal::new([4, 4]);
+        term.checker_fill(GridRect::from_bl([-2, 0], [6u32, 4]), a, b, 2);
+        term.assert_looks_like(
+            "
+oo##
+oo##
+##oo
+##oo",
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn checker_fill_zero_cell() {
+        let mut term = Terminal::new([4, 4]);
+        term.checker_fill(
+            GridRect::from_bl([0, 0], [4u32, 4]),
+            Tile::default(),
+            Tile::default(),
+            0,
+        );
+    }
+
+    #[test]
+    fn draw_glyph_palette() {
+        let mut term = Terminal::new([16, 16]);
+        term.draw_glyph_palette(IVec2::new(0, 15));
+        // 'A' is index 65, the second glyph of the fifth row
+        assert_eq!('A', term.get_char([1, 11]));
+        assert_eq!('☺', term.get_char([1, 15]));
+        assert_eq!('■', term.get_char([14, 0]));
+
+        // Clipped to the terminal
+        let mut term = Terminal::new([4, 4]);
+        term.draw_glyph_palette(IVec2::new(-1, 7));
+        assert_eq!('A', term.get_char([0, 3]));
+    }
+
+    #[test]
+    fn draw_path_arrows() {
+        let mut term = Terminal::new([6, 6]);
+        let path = [IVec2::new(0, 1), IVec2::new(3, 1), IVec2::new(3, 3)];
+        term.draw_path_arrows(&path, Color::YELLOW);
+
+        assert_eq!("→→→", term.get_string([0, 1], 3));
+        assert_eq!('↑', term.get_char([3, 1]));
+        assert_eq!('↑', term.get_char([3, 2]));
+        assert_eq!('•', term.get_char([3, 3]));
+        assert_eq!(Color::YELLOW, term.get_tile([1, 1]).fg_color);
+    }
+
+    #[test]
+    fn line_points_diagonal() {
+        let points: Vec<_> = line_points(IVec2::new(0, 0), IVec2::new(3, -3)).collect();
+        assert_eq!(
+            vec![[0, 0], [1, -1], [2, -2], [3, -3]],
+            points.iter().map(|p| p.to_array()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn term_print() {
+        let mut term = Terminal::new([20, 3]);
+        term_print!(term, [0, 1], "HP: {}/{}", 100, 100);
+        assert_eq!("HP: 100/100", term.get_string([0, 1], 11));
+
+        let capacity = term.string_buffer.capacity();
+        for hp in 0..100 {
+            term_print!(term, [0, 1], "HP: {}/{}", hp, 100);
+        }
+        assert_eq!(capacity, term.string_buffer.capacity());
+        assert_eq!("HP: 99/100", term.get_string([0, 1], 10));
+    }
+
+    #[test]
+    fn dirty() {
+        let mut term = Terminal::new([5, 5]);
+        assert!(term.take_dirty());
+        assert!(!term.take_dirty());
+
+        let _ = term.get_string([0, 0], 3);
+        assert!(!term.is_dirty());
+
+        term.put_string([0, 0], "hi");
+        assert!(term.take_dirty());
+
+        term[[1, 1]].glyph = 'a';
+        assert!(term.take_dirty());
+
+        term.set_border(Border::single_line());
+        assert!(term.take_dirty());
+    }
+
+    #[test]
+    fn cleared() {
+        let mut term = Terminal::new([5, 5]);
+        assert_eq!(Some(term.clear_tile), term.filled_tile());
+
+        term.put_char([1, 1], 'a');
+        assert_eq!(None, term.filled_tile());
+
+        term.clear();
+        assert!(term.filled_tile().is_some());
+        term.set_clear_tile('.');
+        assert_eq!(Some(Tile::from('.')), term.filled_tile());
+        assert!(term.iter().all(|t| t.glyph == '.'));
+
+        term.reset_colors();
+        assert_eq!(None, term.filled_tile());
+        term.resize([3, 3]);
+        assert_eq!(Some(term.clear_tile), term.filled_tile());
+
+        term.fill('#');
+        assert_eq!(Some(Tile::from('#')), term.filled_tile());
+        assert!(term.iter().all(|t| t.glyph == '#'));
+        // Changing the clear tile doesn't touch a terminal filled with
+        // something else
+        term.set_clear_tile('x');
+        assert_eq!(Some(Tile::from('#')), term.filled_tile());
+    }
+
+    #[test]
+    fn apply_palette() {
+        let mut term = Terminal::new([5, 1]);
+        term.put_char([0, 0], 'a'.fg(Color::RED));
+        term.put_char([1, 0], 'b'.bg(Color::RED));
+        term.put_char([2, 0], 'c'.fg(Color::GREEN));
+
+        term.apply_palette(&[(Color::RED, Color::BLUE)]);
+
+        assert_eq!(Color::BLUE, term.get_tile([0, 0]).fg_color);
+        assert_eq!(Color::BLUE, term.get_tile([1, 0]).bg_color);
+        assert_eq!(Tile::DEFAULT_FGCOL, term.get_tile([1, 0]).fg_color);
+        assert_eq!(Color::GREEN, term.get_tile([2, 0]).fg_color);
+        assert_eq!(Tile::DEFAULT_BGCOL, term.get_tile([2, 0]).bg_color);
+    }
+
+    #[test]
+    fn lines() {
+        let mut term = Terminal::new([10, 3]);
+        term.put_string([0, 2], "Hello");
+        term.put_string([2, 1], "World");
+
+        let lines: Vec<_> = term.lines().collect();
+        assert_eq!(vec!["Hello", "  World", ""], lines);
+    }
+
+    #[test]
+    fn fill_with() {
+        let mut term = Terminal::new([4, 2]);
+        term.fill_with(|p| Tile::from(if (p.x + p.y) % 2 == 0 { '#' } else { '.' }));
+
+        assert_eq!("#.#.", term.get_string([0, 0], 4));
+        assert_eq!(".#.#", term.get_string([0, 1], 4));
+    }
+
+    #[test]
+    fn blend_tile() {
+        let black = Tile {
+            glyph: '.',
+            fg_color: Color::BLACK,
+            bg_color: Color::BLACK,
+        };
+        let red = Tile {
+            glyph: 'x',
+            fg_color: Color::RED,
+            bg_color: Color::rgba(1.0, 0.0, 0.0, 0.0),
+        };
+        let mut term = Terminal::new([2, 1]);
+        term.fill_with(|_| black);
+
+        term.blend_tile([0, 0], red, 0.5);
+        let t = term.get_tile([0, 0]);
+        assert_eq!(Color::rgba(0.5, 0.0, 0.0, 1.0), t.fg_color);
+        assert_eq!(Color::rgba(0.5, 0.0, 0.0, 0.5), t.bg_color);
+        assert_eq!('x', t.glyph);
+
+        term.blend_tile([1, 0], red, 0.25);
+        assert_eq!('.', term.get_char([1, 0]));
+        term.blend_tile([1, 0], red, 2.0);
+        assert_eq!(red, *term.get_tile([1, 0]));
+
+        term.blend_tile([5, 0], red, 1.0);
+    }
+
+    #[test]
+    fn swap_tiles() {
+        let mut term = Terminal::new([5, 5]);
+        term.put_char([1, 1], 'a'.fg(Color::RED));
+        term.put_char([3, 2], 'b');
+
+        term.swap_tiles([1, 1], [3, 2]);
+        assert_eq!('b', term.get_char([1, 1]));
+        assert_eq!('a', term.get_char([3, 2]));
+        assert_eq!(Color::RED, term.get_tile([3, 2]).fg_color);
+
+        // Out of bounds - nothing should change
+        term.swap_tiles([1, 1], [10, 10]);
+        assert_eq!('b', term.get_char([1, 1]));
+    }
+
+    #[test]
+    fn move_tile() {
+        let mut term = Terminal::new([5, 5]).with_clear_tile('.');
+        term.put_char([1, 1], 'a');
+
+        term.move_tile([1, 1], [4, 4]);
+        assert_eq!('a', term.get_char([4, 4]));
+        assert_eq!(term.clear_tile, *term.get_tile([1, 1]));
+
+        term.move_tile([4, 4], [-1, 4]);
+        assert_eq!('a', term.get_char([4, 4]));
+    }
+
+    #[test]
+    fn diff() {
+        let prev = Terminal::new([10, 10]);
+        let mut term = prev.clone();
+        assert!(term.diff(&prev).is_empty());
+
+        term.put_char([2, 3], 'a');
+        term.put_char([8, 1], 'b'.fg(Color::RED));
+
+        let diff = term.diff(&prev);
+        assert_eq!(2, diff.len());
+        assert_eq!((IVec2::new(8, 1), *term.get_tile([8, 1])), diff[0]);
+        assert_eq!((IVec2::new(2, 3), *term.get_tile([2, 3])), diff[1]);
+
+        let prev = Terminal::new([5, 5]);
+        assert_eq!(100, term.diff(&prev).len());
+    }
+
+    #[test]
+    fn put_string_clipped() {
+        let mut term = Terminal::new([10, 3]).with_clear_tile('.');
+        term.put_string([7, 1], "Hello");
+        term.put_string([-2, 0], "Hello");
+        // Nothing should have wrapped onto the next line
+        term.assert_looks_like(
+            "
+..........
+.......Hel
+llo.......",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "row 0 (line 2 of the text)")]
+    fn assert_looks_like_mismatch() {
+        let mut term = Terminal::new([3, 2]);
+        term.put_string([0, 0], "abc");
+        term.assert_looks_like("\nabd");
+    }
+
+    #[test]
+    fn put_string_shadowed() {
+        let mut term = Terminal::new([10, 10]);
+        term.put_string_shadowed(
+            [2, 5],
+            "Hi".fg(Color::WHITE),
+            IVec2::new(1, -1),
+            Color::GRAY,
+        );
+
+        assert_eq!("Hi", term.get_string([2, 5], 2));
+        assert_eq!(Color::WHITE, term.get_tile([2, 5]).fg_color);
+        assert_eq!(Color::WHITE, term.get_tile([3, 5]).fg_color);
+
+        assert_eq!("Hi", term.get_string([3, 4], 2));
+        assert_eq!(Color::GRAY, term.get_tile([3, 4]).fg_color);
+        assert_eq!(Color::GRAY, term.get_tile([4, 4]).fg_color);
+
+        // Shadow is clipped at the edge of the terminal
+        term.put_string_shadowed([8, 0], "Hi", IVec2::new(1, -1), Color::GRAY);
+        assert_eq!("Hi", term.get_string([8, 0], 2));
+    }
+
+    #[test]
+    fn map_tiles() {
+        let mut term = Terminal::new([4, 3]);
+        term.map_tiles(|p, t| t.glyph = char::from_digit((p.y * 4 + p.x) as u32, 16).unwrap());
+        assert_eq!("0123", term.get_string([0, 0], 4));
+        assert_eq!("89ab", term.get_string([0, 2], 4));
+    }
+
+    #[test]
+    fn draw_box_merge() {
+        let border = Border::single_line();
+        let mut term = Terminal::new([7, 3]);
+        term.draw_box([0, 0], [4, 3], &border, true);
+        term.draw_box([3, 0], [4, 3], &border, true);
+        assert_eq!("┌──┬──┐", term.get_string([0, 2], 7));
+        assert_eq!("│  │  │", term.get_string([0, 1], 7));
+        assert_eq!("└──┴──┘", term.get_string([0, 0], 7));
+
+        // A 2x2 grid of cells
+        let mut term = Terminal::new([5, 5]);
+        for xy in [[0, 0], [2, 0], [0, 2], [2, 2]] {
+            term.draw_box(xy, [3, 3], &border, true);
+        }
+        assert_eq!("├─┼─┤", term.get_string([0, 2], 5));
+        assert_eq!("┌─┬─┐", term.get_string([0, 4], 5));
+
+        // Without merging the second box overwrites the shared edge
+        let mut term = Terminal::new([7, 3]);
+        term.draw_box([0, 0], [4, 3], &border, false);
+        term.draw_box([3, 0], [4, 3], &border, false);
+        assert_eq!("┌──┌──┐", term.get_string([0, 2], 7));
+
+        // Different line styles aren't merged
+        term.draw_box([0, 0], [4, 3], &Border::double_line(), true);
+        assert_eq!('╗', term.get_char([3, 2]));
+    }
+
+    #[test]
+    fn flood_fill() {
+        let mut term = Terminal::new([5, 3]);
+        term.put_string([0, 1], "ab#ab");
+        term.put_char([0, 0], 'a'.fg(Color::RED));
+        term.put_char([1, 0], 'a');
+
+        // Colors must match, so the red 'a' blocks the fill
+        term.flood_fill([1, 0], Tile::from('x'), FloodFillOptions::default());
+        assert_eq!('a', term.get_char([0, 0]));
+        assert_eq!('x', term.get_char([1, 0]));
+        assert_eq!(' ', term.get_char([2, 0]));
+
+        let mut term = Terminal::new([5, 3]);
+        term.put_char([0, 0], 'a'.fg(Color::RED));
+        term.put_char([1, 0], 'a');
+        term.put_char([1, 1], 'a');
+        term.put_char([2, 1], '#');
+
+        term.flood_fill([1, 0], Tile::from('x'), FloodFillOptions::glyph_only());
+        assert_eq!("xx", term.get_string([0, 0], 2));
+        assert_eq!('x', term.get_char([1, 1]));
+        assert_eq!('#', term.get_char([2, 1]));
+        assert_eq!(' ', term.get_char([2, 0]));
+        assert_eq!(3, term.iter().filter(|t| t.glyph == 'x').count());
+    }
+
+    #[test]
+    fn srgb_colors() {
+        let mut tile = Tile::default();
+        tile.fg_srgb([0.5, 0.5, 0.5]);
+        tile.bg_srgb([1.0, 0.0, 0.0]);
+
+        // sRGB mid-gray is roughly 0.214 in linear space, which is what
+        // the renderer writes to the mesh
+        let [r, g, b, a] = tile.fg_color.as_linear_rgba_f32();
+        for c in [r, g, b] {
+            assert!((c - 0.214).abs() < 0.001, "{c}");
+        }
+        assert_eq!(1.0, a);
+        assert_eq!([1.0, 0.0, 0.0, 1.0], tile.bg_color.as_linear_rgba_f32());
+    }
+
+    #[test]
+    fn same_glyph() {
+        let a = Tile::from('a');
+        let mut b = a;
+        b.fg_color = Color::RED;
+        assert!(a.same_glyph(&b));
+        assert_ne!(a, b);
+        assert!(!a.same_glyph(&Tile::from('b')));
+    }
+
+    #[test]
+    fn iter_border() {
+        let term = Terminal::new([5, 5]);
+        assert_eq!(16, term.iter_border().count());
+
+        let points: Vec<_> = Terminal::new([3, 2])
+            .iter_border()
+            .map(|(p, _)| p)
+            .collect();
+        let expected = [[0, 1], [1, 1], [2, 1], [2, 0], [1, 0], [0, 0]];
+        assert_eq!(expected.map(IVec2::from).to_vec(), points);
+
+        assert_eq!(4, Terminal::new([4, 1]).iter_border().count());
+        assert_eq!(3, Terminal::new([1, 3]).iter_border().count());
+        assert_eq!(1, Terminal::new([1, 1]).iter_border().count());
+    }
+
+    #[test]
+    fn shift_row() {
+        let mut term = Terminal::new([5, 2]);
+        term.put_string([0, 1], "abcde");
+        term.put_string([0, 0], "fghij");
+
+        term.shift_row(1, 1);
+        assert_eq!("eabcd", term.get_string([0, 1], 5));
+        assert_eq!("fghij", term.get_string([0, 0], 5));
+
+        term.shift_row(1, -2);
+        assert_eq!("bcdea", term.get_string([0, 1], 5));
+        term.shift_row(1, 5);
+        assert_eq!("bcdea", term.get_string([0, 1], 5));
+    }
+
+    #[test]
+    fn shift_column() {
+        let mut term = Terminal::new([2, 3]);
+        term.put_char([0, 0], 'a');
+        term.put_char([0, 1], 'b');
+        term.put_char([0, 2], 'c');
+
+        term.shift_column(0, 1);
+        let column: String = term.iter_column(0).map(|t| t.glyph).collect();
+        assert_eq!("cab", column);
+
+        term.shift_column(0, -1);
+        let column: String = term.iter_column(0).map(|t| t.glyph).collect();
+        assert_eq!("abc", column);
+        assert!(term.iter_column(1).all(|t| t.glyph == ' '));
+    }
+
+    #[test]
+    fn invert_rect() {
+        let mut term = Terminal::new([4, 1]);
+        term.put_string([0, 0], "ab".fg(Color::RED).bg(Color::BLUE));
+        let original: Vec<_> = term.iter().copied().collect();
+
+        let rect = GridRect::from_bl([1, 0], [5u32, 1]);
+        term.invert_rect(rect);
+        assert_eq!(original[0], *term.get_tile([0, 0]));
+        let t = term.get_tile([1, 0]);
+        assert_eq!('b', t.glyph);
+        assert_eq!(Color::BLUE, t.fg_color);
+        assert_eq!(Color::RED, t.bg_color);
+        assert_eq!(Tile::DEFAULT_BGCOL, term.get_tile([2, 0]).fg_color);
+
+        term.invert_rect(rect);
+        assert!(term.iter().eq(original.iter()));
+    }
+
+    #[test]
+    fn draw_vertical_bar() {
+        let mut term = Terminal::new([2, 4]);
+        term.draw_vertical_bar(
+            GridRect::from_bl([0, 0], [1u32, 1]),
+            0.375,
+            Color::RED,
+            Color::BLUE,
+        );
+        assert_eq!('▃', term.get_char([0, 0]));
+        assert_eq!(Color::RED, term.get_tile([0, 0]).fg_color);
+        assert_eq!(Color::BLUE, term.get_tile([0, 0]).bg_color);
+
+        let rect = GridRect::from_bl([1, 0], [1u32, 4]);
+        term.draw_vertical_bar(rect, 0.375, Color::RED, Color::BLUE);
+        let column: String = term.iter_column(1).map(|t| t.glyph).collect();
+        assert_eq!("█▄  ", column);
+
+        term.draw_vertical_bar(rect, 1.5, Color::RED, Color::BLUE);
+        let column: String = term.iter_column(1).map(|t| t.glyph).collect();
+        assert_eq!("████", column);
+    }
+
+    #[test]
+    fn iter_rect_clipped() {
+        let mut term = Terminal::new([5, 5]);
+
+        let rect = GridRect::from_bl([3, 3], [5u32, 5]);
+        for t in term.iter_rect_mut(rect) {
+            t.glyph = 'x';
+        }
+        assert_eq!(4, term.iter_rect(rect).count());
+        assert_eq!(4, term.iter().filter(|t| t.glyph == 'x').count());
+        assert_eq!('x', term.get_char([4, 4]));
+
+        let rect = GridRect::from_bl([-3, -3], [2u32, 2]);
+        assert_eq!(0, term.iter_rect(rect).count());
+        let rect = GridRect::from_bl([6, 1], [2u32, 2]);
+        assert_eq!(0, term.iter_rect_mut(rect).count());
+    }
+
+    #[test]
+    fn wrap_addressing() {
+        let mut term = Terminal::new([5, 3]);
+        term.wrap_addressing = true;
+
+        term.put_char([-1, 0], 'a');
+        assert_eq!('a', term.get_char([4, 0]));
+        term.put_char([0, -1], 'b');
+        assert_eq!('b', term.get_char([0, 2]));
+        term.put_char([7, 4], 'c');
+        assert_eq!('c', term.get_char([2, 1]));
+        term[[-6, -4]] = Tile::from('d');
+        assert_eq!('d', term.get_char([4, 2]));
+        assert_eq!('a', term.get_char([-11, 3]));
+
+        assert!(!term.in_bounds([-1, 0]));
+    }
+
+    #[test]
+    fn cells_round_trip() {
+        let mut term = Terminal::new([4, 3]);
+        term.put_char([0, 0], 'a'.fg(Color::RED).bg(Color::BLUE));
+        term.put_char([1, 0], 'b');
+        term.put_char([0, 2], 'c'.fg(Color::rgba(0.1, 0.2, 0.3, 0.4)));
+
+        let cells = term.to_cells();
+        assert_eq!(12, cells.len());
+        // Row-major from the bottom left
+        assert_eq!(('a', [1., 0., 0., 1.], [0., 0., 1., 1.]), cells[0]);
+        assert_eq!('b', cells[1].0);
+        assert_eq!(('c', [0.1, 0.2, 0.3, 0.4]), (cells[8].0, cells[8].1));
+
+        let copy = Terminal::from_cells([4, 3], &cells);
+        assert_eq!(term.size(), copy.size());
+        assert!(term.iter().eq(copy.iter()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_cells_wrong_size() {
+        Terminal::from_cells([2, 2], &[(' ', [0.; 4], [0.; 4])]);
+    }
+
+    #[test]
+    fn to_string() {
+        let mut term = Terminal::new([6, 3]);
+        term.put_string([0, 2], "Hello");
+        term.put_string([1, 0], "abc");
+        assert_eq!("Hello\n\n abc", term.to_string());
+    }
+
+    #[test]
+    fn draw_grid() {
+        let mut term = Terminal::new([9, 6]);
+        term.draw_grid([4, 4], '+', Color::GRAY);
+        let lines: Vec<_> = term.lines().collect();
+        // Lines are returned from the top down
+        assert_eq!(
+            vec![
+                "+   +   +",
+                "+++++++++",
+                "+   +   +",
+                "+   +   +",
+                "+   +   +",
+                "+++++++++"
+            ],
+            lines
+        );
+        assert_eq!(Color::GRAY, term.get_tile([4, 1]).fg_color);
+        assert_eq!(Color::WHITE, term.get_tile([1, 1]).fg_color);
+
+        term.draw_grid([1, 1], '.', Color::GRAY);
+        assert!(term.iter().all(|t| t.glyph == '.'));
+    }
+
+    #[test]
+    #[should_panic]
+    fn draw_grid_zero_spacing() {
+        Terminal::new([4, 4]).draw_grid([0, 2], '+', Color::GRAY);
+    }
+
+    #[test]
+    fn map_region() {
+        let mut term = Terminal::new([10, 10]);
+        term.put_string([0, 2], "ab cd");
+        term.put_string([0, 8], "ef");
+
+        let rect = GridRect::from_bl([-2, 0], [5u32, 5]);
+        term.map_region(rect, |_, t| {
+            if t.glyph != ' ' {
+                t.fg_color = Color::RED;
+            }
+        });
+
+        assert_eq!(Color::RED, term.get_tile([0, 2]).fg_color);
+        assert_eq!(Color::RED, term.get_tile([1, 2]).fg_color);
+        assert_eq!(Tile::DEFAULT_FGCOL, term.get_tile([2, 2]).fg_color);
+        assert_eq!(Tile::DEFAULT_FGCOL, term.get_tile([3, 2]).fg_color);
+        assert_eq!(Tile::DEFAULT_FGCOL, term.get_tile([0, 8]).fg_color);
+    }
+
+    #[test]
+    fn scramble_glyphs() {
+        let mut term = Terminal::new([12, 3]);
+        term.put_string([1, 1], "abcdefghij".fg(Color::RED));
+        let rect = GridRect::from_bl([1, 1], [10u32, 1]);
+
+        let mut a = term.clone();
+        a.scramble_glyphs(rect, 7);
+        let mut b = term.clone();
+        b.scramble_glyphs(rect, 7);
+        let mut c = term.clone();
+        c.scramble_glyphs(rect, 8);
+
+        let a_str = a.get_string([1, 1], 10);
+        assert_eq!(a_str, b.get_string([1, 1], 10));
+        assert_ne!(a_str, c.get_string([1, 1], 10));
+        assert_ne!("abcdefghij", a_str);
+
+        let mut sorted: Vec<_> = a_str.chars().collect();
+        sorted.sort();
+        assert_eq!("abcdefghij", String::from_iter(sorted));
+        assert!(a
+            .iter_row(1)
+            .skip(1)
+            .take(10)
+            .all(|t| t.fg_color == Color::RED));
+    }
+
+    #[test]
+    fn count_glyph() {
+        let mut term = Terminal::new([10, 5]);
+        assert_eq!(50, term.count_glyph(' '));
+
+        term.put_char([3, 2], '#');
+        assert_eq!(49, term.count_glyph(' '));
+        assert_eq!(1, term.count_glyph('#'));
+    }
+
+    #[test]
+    fn find_glyph() {
+        let mut term = Terminal::new([10, 5]);
+        assert_eq!(None, term.find_glyph('@'));
+
+        term.put_char([7, 3], '@');
+        assert_eq!(Some(IVec2::new(7, 3)), term.find_glyph('@'));
+
+        term.put_char([2, 1], '@');
+        let positions: Vec<_> = term.positions_of_glyph('@').collect();
+        assert_eq!(vec![IVec2::new(2, 1), IVec2::new(7, 3)], positions);
+    }
 }
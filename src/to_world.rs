@@ -2,18 +2,18 @@
 //! and world space.
 
 use bevy::{
-    math::{IVec2, Mat4, UVec2, Vec2, Vec3},
+    math::{IVec2, Mat4, Rect, UVec2, Vec2, Vec3},
     prelude::{
         App, Assets, Camera, Changed, Component, Entity, GlobalTransform, Image, Or, Plugin, Query,
-        Res, Update, With,
+        RemovedComponents, Res, Update, With,
     },
     render::camera::{ManualTextureViews, RenderTarget},
     window::{PrimaryWindow, Window, WindowRef},
 };
-use sark_grids::GridPoint;
+use sark_grids::{geometry::GridRect, GridPoint};
 
 use crate::{
-    renderer::{TerminalLayout, TileScaling},
+    renderer::{TerminalLayout, TerminalScrollOffset, TileScaling},
     Terminal,
 };
 
@@ -21,7 +21,14 @@ pub(crate) struct ToWorldPlugin;
 
 impl Plugin for ToWorldPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (update_from_terminal, update_from_camera));
+        app.add_systems(
+            Update,
+            (
+                update_from_terminal,
+                reset_scroll_offset,
+                update_from_camera,
+            ),
+        );
     }
 }
 
@@ -34,6 +41,7 @@ impl Plugin for ToWorldPlugin {
 pub struct ToWorld {
     term_size: UVec2,
     term_pos: Vec3,
+    scroll_offset: Vec2,
     layout: TerminalLayout,
     camera_entity: Option<Entity>,
     ndc_to_world: Mat4,
@@ -46,9 +54,7 @@ impl ToWorld {
     /// Convert a tile position (bottom left corner) to it's corresponding
     /// world position.
     pub fn tile_to_world(&self, tile: impl GridPoint) -> Vec3 {
-        let term_pos = self.term_pos.truncate();
-        let term_offset = self.term_size.as_vec2() * Vec2::from(self.layout.pivot);
-        (tile.as_vec2() + term_pos - term_offset).extend(self.term_pos.z)
+        (tile.as_vec2() + self.tile_origin()).extend(self.term_pos.z)
     }
 
     /// Convert a tile center to it's corresponding world position.
@@ -58,10 +64,23 @@ impl ToWorld {
     }
 
     pub fn world_to_tile(&self, world: Vec2) -> IVec2 {
+        self.world_to_tile_f32(world).floor().as_ivec2()
+    }
+
+    /// Convert a world position to a fractional tile position, IE: `[2.5, 3.0]`
+    /// is the horizontal center of the bottom edge of tile `[2, 3]`.
+    ///
+    /// Useful for smoothly animating something between tiles.
+    pub fn world_to_tile_f32(&self, world: Vec2) -> Vec2 {
+        world - self.tile_origin()
+    }
+
+    /// The world position of the bottom left corner of tile `[0, 0]`,
+    /// accounting for the terminal's pivot and [`TerminalScrollOffset`].
+    fn tile_origin(&self) -> Vec2 {
         let term_pos = self.term_pos.truncate();
         let term_offset = self.term_size.as_vec2() * Vec2::from(self.layout.pivot);
-        let xy = world - term_pos + term_offset;
-        xy.floor().as_ivec2()
+        term_pos - term_offset + self.scroll_offset * self.world_unit()
     }
 
     /// The size of a single world unit, accounting for `TileScaling`.
@@ -72,6 +91,22 @@ impl ToWorld {
         }
     }
 
+    /// The world space area covered by the given tiles, IE: for drawing a
+    /// highlight around a selected region.
+    ///
+    /// The tiles are clipped to the terminal bounds. Returns `None` if no
+    /// part of `rect` is inside the terminal.
+    pub fn tile_rect_to_world(&self, rect: GridRect) -> Option<Rect> {
+        let min = rect.min_i().max(IVec2::ZERO);
+        let max = rect.max_i().min(self.term_size.as_ivec2() - 1);
+        if min.cmpgt(max).any() {
+            return None;
+        }
+        let size = (max - min + 1).as_vec2() * self.world_unit();
+        let min = self.tile_to_world(min).truncate();
+        Some(Rect::from_corners(min, min + size))
+    }
+
     /// Convert a position from screen space (ie: Cursor position) to world space.
     pub fn screen_to_world(&self, screen_pos: Vec2) -> Option<Vec2> {
         if let Some(viewport_size) = self.viewport_size {
@@ -88,19 +123,83 @@ impl ToWorld {
             None
         }
     }
+
+    /// Returns the area of the terminal, in tile positions, which is currently
+    /// visible within the camera's viewport.
+    ///
+    /// Returns `None` if the viewport size is unknown or no part of the
+    /// terminal is visible.
+    pub fn visible_tile_rect(&self) -> Option<GridRect> {
+        let viewport_size = self.viewport_size?;
+        let a = self.screen_to_world(self.viewport_pos)?;
+        let b = self.screen_to_world(self.viewport_pos + viewport_size)?;
+
+        let origin = self.tile_origin();
+        let min = (a.min(b) - origin).floor().as_ivec2();
+        let max = (a.max(b) - origin).ceil().as_ivec2() - 1;
+
+        let min = min.max(IVec2::ZERO);
+        let max = max.min(self.term_size.as_ivec2() - 1);
+        if min.cmpgt(max).any() {
+            return None;
+        }
+        Some(GridRect::from_bl(min, (max - min + 1).as_uvec2()))
+    }
+
+    /// Convert a position from world space to screen space. This is the
+    /// inverse of [`ToWorld::screen_to_world`].
+    pub fn world_to_screen(&self, world_pos: Vec2) -> Option<Vec2> {
+        if let Some(viewport_size) = self.viewport_size {
+            // convert world-space coordinates to ndc [-1..1]
+            let ndc = self
+                .ndc_to_world
+                .inverse()
+                .project_point3(world_pos.extend(0.0))
+                .truncate();
+
+            // convert ndc to screen position [0..resolution]
+            let screen_pos = (ndc + Vec2::ONE) / 2.0 * viewport_size;
+
+            Some(screen_pos + self.viewport_pos)
+        } else {
+            None
+        }
+    }
 }
 
 #[allow(clippy::type_complexity)]
 fn update_from_terminal(
     mut q_term: Query<
-        (&mut ToWorld, &Terminal, &GlobalTransform, &TerminalLayout),
-        Or<(Changed<Terminal>, Changed<TerminalLayout>)>,
+        (
+            &mut ToWorld,
+            &Terminal,
+            &GlobalTransform,
+            &TerminalLayout,
+            Option<&TerminalScrollOffset>,
+        ),
+        Or<(
+            Changed<Terminal>,
+            Changed<TerminalLayout>,
+            Changed<TerminalScrollOffset>,
+        )>,
     >,
 ) {
-    for (mut to_world, term, transform, layout) in q_term.iter_mut() {
+    for (mut to_world, term, transform, layout, scroll) in q_term.iter_mut() {
         to_world.term_size = term.size();
         to_world.layout = layout.clone();
         to_world.term_pos = transform.translation();
+        to_world.scroll_offset = scroll.map_or(Vec2::ZERO, |s| s.0);
+    }
+}
+
+fn reset_scroll_offset(
+    mut removed: RemovedComponents<TerminalScrollOffset>,
+    mut q_to_world: Query<&mut ToWorld>,
+) {
+    for entity in removed.read() {
+        if let Ok(mut to_world) = q_to_world.get_mut(entity) {
+            to_world.scroll_offset = Vec2::ZERO;
+        }
     }
 }
 
@@ -134,24 +233,25 @@ fn update_from_camera(
             tw.camera_pos = t.translation();
             tw.ndc_to_world = t.compute_matrix() * cam.projection_matrix().inverse();
 
+            let window = match &cam.target {
+                RenderTarget::Window(WindowRef::Primary) => primary_window.get_single().ok(),
+                RenderTarget::Window(WindowRef::Entity(win_entity)) => {
+                    windows.get(*win_entity).ok()
+                }
+                _ => None,
+            };
+
             if let Some(vp) = &cam.viewport {
-                tw.viewport_pos = vp.physical_position.as_vec2();
-                tw.viewport_size = Some(vp.physical_size.as_vec2());
+                // Cursor positions are in logical pixels, but the viewport is
+                // in physical pixels
+                let scale = window.map(|w| w.scale_factor()).unwrap_or(1.0);
+                tw.viewport_pos = vp.physical_position.as_vec2() / scale;
+                tw.viewport_size = Some(vp.physical_size.as_vec2() / scale);
             } else {
                 tw.viewport_pos = Vec2::ZERO;
                 let res = match &cam.target {
-                    RenderTarget::Window(win_ref) => {
-                        let window = match win_ref {
-                            WindowRef::Primary => primary_window.get_single().ok(),
-                            WindowRef::Entity(win_entity) => windows.get(*win_entity).ok(),
-                        };
-
+                    RenderTarget::Window(_) => {
                         window.map(|window| Vec2::new(window.width(), window.height()))
-                        // if let Some(window) = windows.get(*win_id) {
-                        //     Some(Vec2::new(window.width(), window.height()))
-                        // } else {
-                        //     None
-                        // }
                     }
                     RenderTarget::Image(image) => {
                         images.get(image).map(|image| image.size().as_vec2())
@@ -170,3 +270,189 @@ fn update_from_camera(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        math::{Mat4, UVec2, Vec2, Vec3},
+        prelude::{App, Assets, Camera, GlobalTransform, Image, Transform, Update},
+        render::camera::{ManualTextureViews, Viewport},
+        window::{PrimaryWindow, Window, WindowResolution},
+    };
+
+    use super::*;
+
+    #[test]
+    fn world_to_screen_round_trip() {
+        let proj = Mat4::orthographic_rh(-40.0, 40.0, -12.5, 12.5, 0.0, 1000.0);
+        let cam = Mat4::from_translation(Vec3::new(3.0, -2.0, 999.0));
+        let to_world = ToWorld {
+            ndc_to_world: cam * proj.inverse(),
+            viewport_pos: Vec2::new(16.0, 8.0),
+            viewport_size: Some(Vec2::new(640.0, 200.0)),
+            ..Default::default()
+        };
+
+        for p in [
+            Vec2::ZERO,
+            Vec2::new(10.5, 3.25),
+            Vec2::new(-35.0, 11.0),
+            Vec2::new(42.0, -14.0),
+        ] {
+            let screen = to_world.world_to_screen(p).unwrap();
+            let world = to_world.screen_to_world(screen).unwrap();
+            assert!((world - p).length() < 0.001, "{} != {}", world, p);
+        }
+    }
+
+    #[test]
+    fn world_to_tile_f32() {
+        let to_world = ToWorld {
+            term_size: UVec2::new(10, 10),
+            ..Default::default()
+        };
+
+        // Centered terminal, world origin is the corner between four tiles
+        assert_eq!(Vec2::new(5.0, 5.0), to_world.world_to_tile_f32(Vec2::ZERO));
+        assert_eq!(IVec2::new(5, 5), to_world.world_to_tile(Vec2::ZERO));
+
+        let p = to_world.world_to_tile_f32(Vec2::new(0.5, -0.5));
+        assert_eq!(Vec2::new(5.5, 4.5), p);
+        assert_eq!(
+            IVec2::new(5, 4),
+            to_world.world_to_tile(Vec2::new(0.5, -0.5))
+        );
+
+        // The mesh is moved by the scroll offset, so the tiles are too
+        let to_world = ToWorld {
+            scroll_offset: Vec2::new(0.5, -1.0),
+            ..to_world
+        };
+        assert_eq!(Vec2::new(4.5, 6.0), to_world.world_to_tile_f32(Vec2::ZERO));
+        assert_eq!(Vec3::new(0.5, -1.0, 0.0), to_world.tile_to_world([5, 5]));
+    }
+
+    #[test]
+    fn visible_tile_rect() {
+        let to_world = ToWorld {
+            term_size: UVec2::new(40, 40),
+            ndc_to_world: Mat4::from_scale(Vec3::splat(10.0)),
+            viewport_size: Some(Vec2::new(100.0, 100.0)),
+            ..Default::default()
+        };
+
+        let rect = to_world.visible_tile_rect().unwrap();
+        assert_eq!(IVec2::new(10, 10), rect.min_i());
+        assert_eq!(IVec2::new(29, 29), rect.max_i());
+
+        // Move the terminal so only it's bottom left quadrant is in view
+        let to_world = ToWorld {
+            term_pos: Vec3::new(20.0, 20.0, 0.0),
+            ..to_world
+        };
+        let rect = to_world.visible_tile_rect().unwrap();
+        assert_eq!(IVec2::new(0, 0), rect.min_i());
+        assert_eq!(IVec2::new(9, 9), rect.max_i());
+
+        let to_world = ToWorld {
+            term_pos: Vec3::new(100.0, 0.0, 0.0),
+            ..to_world
+        };
+        assert!(to_world.visible_tile_rect().is_none());
+    }
+
+    #[test]
+    fn tile_rect_to_world() {
+        let to_world = ToWorld {
+            term_size: UVec2::new(10, 10),
+            ..Default::default()
+        };
+        let rect = GridRect::from_bl([2, 3], [4u32, 2]);
+        let world = to_world.tile_rect_to_world(rect).unwrap();
+        assert_eq!(Vec2::new(-3.0, -2.0), world.min);
+        assert_eq!(Vec2::new(4.0, 2.0) * to_world.world_unit(), world.size());
+
+        let mut to_world = to_world;
+        to_world.layout.scaling = TileScaling::Pixels;
+        to_world.layout.pixels_per_tile = UVec2::new(8, 8);
+        let world = to_world.tile_rect_to_world(rect).unwrap();
+        assert_eq!(Vec2::new(32.0, 16.0), world.size());
+
+        // Clipped to the terminal
+        let rect = GridRect::from_bl([8, -2], [4u32, 4]);
+        let world = to_world.tile_rect_to_world(rect).unwrap();
+        assert_eq!(Vec2::new(16.0, 16.0), world.size());
+        let rect = GridRect::from_bl([10, 0], [2u32, 2]);
+        assert!(to_world.tile_rect_to_world(rect).is_none());
+    }
+
+    #[test]
+    fn tile_under_cursor() {
+        let to_world = ToWorld {
+            term_size: UVec2::new(10, 10),
+            ndc_to_world: Mat4::from_scale(Vec3::splat(10.0)),
+            viewport_size: Some(Vec2::new(100.0, 100.0)),
+            ..Default::default()
+        };
+        let mut term = Terminal::new([10, 10]);
+        term.put_char([6, 3], 'x');
+
+        // Screen [57, 43] is world [1.4, -1.4], which is tile [6, 3]
+        let (xy, tile) = term
+            .tile_under_cursor(&to_world, Vec2::new(57.0, 43.0))
+            .unwrap();
+        assert_eq!(IVec2::new(6, 3), xy);
+        assert_eq!('x', tile.glyph);
+
+        // Screen [77, 77] is world [5.4, 5.4], just outside the terminal
+        assert!(term
+            .tile_under_cursor(&to_world, Vec2::new(77.0, 77.0))
+            .is_none());
+    }
+
+    #[test]
+    fn scale_factor() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Image>>()
+            .init_resource::<ManualTextureViews>()
+            .add_systems(Update, update_from_camera);
+
+        app.world.spawn((
+            Window {
+                resolution: WindowResolution::new(800.0, 600.0).with_scale_factor_override(2.0),
+                ..Default::default()
+            },
+            PrimaryWindow,
+        ));
+        app.world.spawn((
+            Camera {
+                viewport: Some(Viewport {
+                    physical_position: UVec2::new(100, 50),
+                    physical_size: UVec2::new(400, 200),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            GlobalTransform::from(Transform::from_scale(Vec3::splat(5.0))),
+        ));
+        let term = app
+            .world
+            .spawn(ToWorld {
+                term_size: UVec2::new(10, 10),
+                ..Default::default()
+            })
+            .id();
+
+        app.update();
+
+        let to_world = app.world.get::<ToWorld>(term).unwrap();
+        assert_eq!(Vec2::new(50.0, 25.0), to_world.viewport_pos);
+        assert_eq!(Some(Vec2::new(200.0, 100.0)), to_world.viewport_size);
+
+        // Logical cursor position three quarters of the way across the viewport
+        let cursor = Vec2::new(200.0, 100.0);
+        let world = to_world.screen_to_world(cursor).unwrap();
+        assert_eq!(Vec2::new(2.5, 2.5), world);
+        assert_eq!(IVec2::new(7, 7), to_world.world_to_tile(world));
+    }
+}
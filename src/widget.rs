@@ -0,0 +1,85 @@
+//! A common interface for things which can be drawn to an area of a terminal.
+use sark_grids::geometry::GridRect;
+
+use crate::{Border, Terminal};
+
+/// Something which can be drawn to an area of a terminal, IE: a box, a bar or
+/// a menu.
+///
+/// Allows different kinds of widgets to be stored and drawn together, for
+/// example as a `Vec<Box<dyn TerminalWidget>>`. Any closure taking a terminal
+/// and an area is also a widget.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::*;
+///
+/// let widgets: Vec<(GridRect, Box<dyn TerminalWidget>)> = vec![
+///     (GridRect::from_bl([0, 0], [10u32, 5]), Box::new(Border::single_line())),
+///     (
+///         GridRect::from_bl([1, 1], [8u32, 3]),
+///         Box::new(|term: &mut Terminal, rect: GridRect| {
+///             term.put_string(rect.min_i(), "Hello");
+///         }),
+///     ),
+/// ];
+///
+/// let mut term = Terminal::new([10, 5]);
+/// for (rect, widget) in &widgets {
+///     widget.draw(&mut term, *rect);
+/// }
+/// ```
+pub trait TerminalWidget {
+    /// Draw the widget to the given area of the terminal.
+    fn draw(&self, term: &mut Terminal, rect: GridRect);
+}
+
+/// Draws a box around the edges of the area.
+impl TerminalWidget for Border {
+    fn draw(&self, term: &mut Terminal, rect: GridRect) {
+        term.draw_box(rect.min_i(), rect.size().as_uvec2(), self, false);
+    }
+}
+
+impl<F: Fn(&mut Terminal, GridRect)> TerminalWidget for F {
+    fn draw(&self, term: &mut Terminal, rect: GridRect) {
+        self(term, rect)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Color;
+
+    use super::*;
+
+    #[test]
+    fn boxed_bar() {
+        let bar = |term: &mut Terminal, rect: GridRect| {
+            term.draw_vertical_bar(rect, 0.5, Color::GREEN, Color::BLACK);
+        };
+        let widgets: Vec<(GridRect, Box<dyn TerminalWidget>)> = vec![
+            (
+                GridRect::from_bl([0, 0], [3u32, 6]),
+                Box::new(Border::single_line()),
+            ),
+            (GridRect::from_bl([1, 1], [1u32, 4]), Box::new(bar)),
+        ];
+
+        let mut term = Terminal::new([3, 6]);
+        for (rect, widget) in &widgets {
+            widget.draw(&mut term, *rect);
+        }
+        term.assert_looks_like(
+            "
+┌─┐
+│ │
+│ │
+│█│
+│█│
+└─┘",
+        );
+    }
+}